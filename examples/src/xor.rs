@@ -27,23 +27,9 @@ fn main() -> anyhow::Result<()> {
     let population_size = 50;
     let mut population = vec![];
 
-    let config = Config {
-        add_node: 0.15,
-        add_connection: 0.15,
-        connection_enabled: 0.1,
-        crossover: 0.3,
-        connection_weight: 1.0,
-        connection_weight_prob: 0.8,
-        connection_weight_delta: 0.1,
-        connection_weight_iter: 5,
-        node_bias_prob: 0.15,
-        node_bias_delta: 0.1,
-        node_bias: 1.0,
-        node_activation_prob: 0.15,
-        connection_max: 10000,
-        node_max: 1000,
-        node_enabled: 0.5,
-    };
+    let config =
+        Config::from_file(concat!(env!("CARGO_MANIFEST_DIR"), "/configs/xor.toml"))?.with_env()?;
+    config.validate(2, 1)?;
 
     let genome = Genome::generate_genome(2, 1, vec![], Some(Activation::Sigmoid), &config)?;
 
@@ -112,7 +98,7 @@ fn main() -> anyhow::Result<()> {
     }
 
     if let Some(best) = best {
-        event!(Level::INFO, "{}", best.genome.as_json());
+        event!(Level::INFO, "{}", best.get_genome().as_json());
     }
 
     Ok(())
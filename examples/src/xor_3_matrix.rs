@@ -42,23 +42,9 @@ fn main() -> anyhow::Result<()> {
     let population_size = 50;
     let mut population = vec![];
 
-    let config = Config {
-        add_node: 0.10,
-        add_connection: 0.25,
-        connection_enabled: 0.25,
-        crossover: 0.15,
-        connection_weight: 2.0,
-        connection_weight_prob: 0.8,
-        connection_weight_delta: 0.35,
-        connection_weight_iter: 25,
-        node_bias_prob: 0.35,
-        node_bias_delta: 0.35,
-        node_bias: 2.0,
-        node_activation_prob: 0.15,
-        connection_max: 10000,
-        node_max: 1000,
-        node_enabled: 0.15,
-    };
+    let config = Config::from_file(concat!(env!("CARGO_MANIFEST_DIR"), "/configs/matrix.toml"))?
+        .with_env()?;
+    config.validate(3, 1)?;
 
     let genome = Genome::generate_genome(3, 1, vec![], Some(Activation::Sigmoid), &config)?;
 
@@ -110,9 +96,9 @@ fn main() -> anyhow::Result<()> {
     }
 
     if let Some(best) = best {
-        info!("{}", best.genome.as_json());
+        info!("{}", best.get_genome().as_json());
 
-        let data = best.genome.to_weights();
+        let data = best.get_genome().to_weights();
 
         let organism = Organism::new(Genome::from_weights(data));
 
@@ -2,8 +2,8 @@ use lazy_static::lazy_static;
 use ndarray::Array2;
 use tracing::{info, level_filters::LevelFilter};
 
-use vivalaakam_neuro_neat::{Config, Genome, Organism};
-use vivalaakam_neuro_shared::{FitnessTrait, OrganismTrait, Population};
+use vivalaakam_neuro_neat::{Config, Genome, Organism, PopulationStats};
+use vivalaakam_neuro_shared::{Dataset, OrganismTrait, Population};
 use vivalaakam_neuro_utils::Activation;
 
 lazy_static! {
@@ -14,37 +14,13 @@ lazy_static! {
         Array2::from_shape_vec((4, 1), vec![0f32, 1f32, 1f32, 0f32]).expect("");
 }
 
-struct Dataset {
-    inputs: Array2<f32>,
-    outputs: Array2<f32>,
-}
-
-impl FitnessTrait for Dataset {
-    fn calculate<T, C>(&self, organism: &T) -> f32
-    where
-        T: OrganismTrait<C>,
-    {
-        let output = organism.activate_matrix(&self.inputs);
-
-        let distance = (&self.outputs - output)
-            .iter()
-            .map(|row| (*row).powi(2))
-            .sum::<f32>();
-
-        16f32 / (1f32 + distance)
-    }
-}
-
 fn main() -> anyhow::Result<()> {
     tracing_subscriber::fmt()
         .with_max_level(LevelFilter::INFO)
         .with_test_writer()
         .init();
 
-    let dataset = Dataset {
-        inputs: INPUTS.clone(),
-        outputs: OUTPUTS.clone(),
-    };
+    let dataset = Dataset::new(INPUTS.clone(), OUTPUTS.clone())?;
 
     let population_size = 50;
 
@@ -67,7 +43,7 @@ fn main() -> anyhow::Result<()> {
     let mut epoch = 0;
     while best.is_none() {
         if let Some(organism) = population.epoch(&dataset) {
-            if organism.get_fitness() > 15.5 {
+            if organism.get_fitness() > 0.99 {
                 best = Some(organism.clone());
             }
 
@@ -77,13 +53,16 @@ fn main() -> anyhow::Result<()> {
                 organism.get_stagnation()
             );
         }
+
+        PopulationStats::new(epoch, population.get_organisms()).emit();
+
         epoch += 1;
     }
 
     if let Some(best) = best {
-        info!("{}", best.genome.as_json());
+        info!("{}", best.get_genome().as_json());
 
-        let data = best.genome.to_weights();
+        let data = best.get_genome().to_weights();
 
         let organism = Organism::new(Genome::from_weights(data));
 
@@ -0,0 +1,22 @@
+use std::path::PathBuf;
+
+use clap::Args;
+
+use crate::genome_file::{self, GenomeFormat};
+
+#[derive(Args)]
+pub struct ConvertArgs {
+    /// Genome file to read; the format is detected.
+    input: PathBuf,
+    /// Genome file to write.
+    output: PathBuf,
+    /// Format of the written genome.
+    #[arg(long, value_enum)]
+    to: GenomeFormat,
+}
+
+pub fn run(args: ConvertArgs) -> anyhow::Result<()> {
+    let genome = genome_file::load(&args.input)?;
+
+    genome_file::save(&args.output, &genome, args.to)
+}
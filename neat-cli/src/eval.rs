@@ -0,0 +1,54 @@
+use std::path::PathBuf;
+
+use clap::Args;
+
+use vivalaakam_neuro_neat::Organism;
+use vivalaakam_neuro_shared::{Metric, OrganismTrait};
+
+use crate::genome_file;
+use crate::DataArgs;
+
+#[derive(Args)]
+pub struct EvalArgs {
+    /// Genome file (`as_json` object or `to_weights` array).
+    #[arg(long)]
+    genome: PathBuf,
+    #[command(flatten)]
+    data: DataArgs,
+    /// Optional CSV file the predictions are written to.
+    #[arg(long)]
+    predictions: Option<PathBuf>,
+}
+
+pub fn run(args: EvalArgs) -> anyhow::Result<()> {
+    let organism = Organism::new(genome_file::load(&args.genome)?);
+    let dataset = args.data.load()?;
+
+    let outputs = organism.activate_matrix(dataset.get_inputs());
+
+    if let Some(path) = &args.predictions {
+        let mut writer = csv::Writer::from_path(path)?;
+        writer.write_record(&args.data.targets)?;
+
+        for row in outputs.rows() {
+            writer.write_record(row.iter().map(|value| value.to_string()))?;
+        }
+
+        writer.flush()?;
+    }
+
+    for (name, metric) in [
+        ("mse", Metric::Mse),
+        ("mae", Metric::Mae),
+        ("cross_entropy", Metric::CrossEntropy),
+        ("accuracy", Metric::Accuracy),
+        ("r2", Metric::R2),
+    ] {
+        println!(
+            "{name}: {}",
+            metric.evaluate(outputs.view(), dataset.get_targets().view())
+        );
+    }
+
+    Ok(())
+}
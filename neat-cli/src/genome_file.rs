@@ -0,0 +1,48 @@
+use std::fs;
+use std::path::Path;
+
+use clap::ValueEnum;
+use serde_json::Value;
+
+use vivalaakam_neuro_neat::{Genome, GENOME_MAGIC};
+
+/// On-disk genome formats.
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum GenomeFormat {
+    /// `Genome::as_json` object.
+    Json,
+    /// JSON array of `Genome::to_weights` values.
+    Weights,
+    /// `Genome::to_bytes` binary format.
+    Binary,
+}
+
+/// Loads a genome, detecting the format from the magic header or the JSON value.
+pub fn load(path: &Path) -> anyhow::Result<Genome> {
+    let data = fs::read(path)?;
+
+    if data.starts_with(GENOME_MAGIC) {
+        return Ok(Genome::from_bytes(&data)?);
+    }
+
+    let data = String::from_utf8(data)?;
+
+    match serde_json::from_str::<Value>(&data)? {
+        Value::Array(_) => Ok(Genome::try_from_weights(
+            &serde_json::from_str::<Vec<f32>>(&data)?,
+        )?),
+        value => Ok(serde_json::from_value(value)?),
+    }
+}
+
+pub fn save(path: &Path, genome: &Genome, format: GenomeFormat) -> anyhow::Result<()> {
+    let data = match format {
+        GenomeFormat::Json => genome.as_json().into_bytes(),
+        GenomeFormat::Weights => serde_json::to_vec(&genome.to_weights())?,
+        GenomeFormat::Binary => genome.to_bytes().to_vec(),
+    };
+
+    fs::write(path, data)?;
+
+    Ok(())
+}
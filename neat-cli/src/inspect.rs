@@ -0,0 +1,26 @@
+use std::path::PathBuf;
+
+use clap::Args;
+
+use crate::genome_file;
+
+#[derive(Args)]
+pub struct InspectArgs {
+    /// Genome file (`as_json` object or `to_weights` array).
+    genome: PathBuf,
+    /// Print a Graphviz DOT graph instead of statistics.
+    #[arg(long)]
+    dot: bool,
+}
+
+pub fn run(args: InspectArgs) -> anyhow::Result<()> {
+    let genome = genome_file::load(&args.genome)?;
+
+    if args.dot {
+        print!("{}", genome.to_dot());
+    } else {
+        println!("{}", serde_json::to_string_pretty(&genome.stats())?);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use tracing::level_filters::LevelFilter;
+
+use vivalaakam_neuro_shared::Metric;
+
+mod convert;
+mod eval;
+mod genome_file;
+mod inspect;
+mod train;
+
+/// Train, evaluate, inspect and convert NEAT genomes.
+#[derive(Parser)]
+#[command(name = "neat-cli", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Evolve a genome on a CSV dataset until the target fitness is reached.
+    Train(train::TrainArgs),
+    /// Run a saved genome on a CSV dataset and print metrics.
+    Eval(eval::EvalArgs),
+    /// Print genome statistics or a Graphviz DOT graph.
+    Inspect(inspect::InspectArgs),
+    /// Convert a genome between JSON, flat weights and binary.
+    Convert(convert::ConvertArgs),
+}
+
+/// CSV dataset with input and target columns selected by name.
+#[derive(Args)]
+pub struct DataArgs {
+    /// CSV file with a header row.
+    #[arg(long)]
+    pub data: PathBuf,
+    /// Comma-separated input column names.
+    #[arg(long, value_delimiter = ',', required = true)]
+    pub inputs: Vec<String>,
+    /// Comma-separated target column names.
+    #[arg(long, value_delimiter = ',', required = true)]
+    pub targets: Vec<String>,
+    /// Metric used as fitness.
+    #[arg(long, value_enum, default_value_t = MetricArg::Mse)]
+    pub metric: MetricArg,
+}
+
+impl DataArgs {
+    pub fn load(&self) -> anyhow::Result<vivalaakam_neuro_shared::Dataset> {
+        let inputs = self.inputs.iter().map(String::as_str).collect::<Vec<_>>();
+        let targets = self.targets.iter().map(String::as_str).collect::<Vec<_>>();
+
+        Ok(
+            vivalaakam_neuro_shared::Dataset::from_csv(&self.data, &inputs, &targets)?
+                .with_metric(self.metric.into()),
+        )
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum MetricArg {
+    Mse,
+    Mae,
+    CrossEntropy,
+    Accuracy,
+    R2,
+}
+
+impl From<MetricArg> for Metric {
+    fn from(value: MetricArg) -> Self {
+        match value {
+            MetricArg::Mse => Metric::Mse,
+            MetricArg::Mae => Metric::Mae,
+            MetricArg::CrossEntropy => Metric::CrossEntropy,
+            MetricArg::Accuracy => Metric::Accuracy,
+            MetricArg::R2 => Metric::R2,
+        }
+    }
+}
+
+fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt()
+        .with_max_level(LevelFilter::INFO)
+        .with_writer(std::io::stderr)
+        .init();
+
+    match Cli::parse().command {
+        Command::Train(args) => train::run(args),
+        Command::Eval(args) => eval::run(args),
+        Command::Inspect(args) => inspect::run(args),
+        Command::Convert(args) => convert::run(args),
+    }
+}
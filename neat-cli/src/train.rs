@@ -0,0 +1,105 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use tracing::info;
+
+use vivalaakam_neuro_neat::{Config, Genome, Organism, PopulationStats};
+use vivalaakam_neuro_shared::{JsonlReporter, OrganismTrait, Population};
+use vivalaakam_neuro_utils::Activation;
+
+use crate::genome_file::{self, GenomeFormat};
+use crate::DataArgs;
+
+#[derive(Args)]
+pub struct TrainArgs {
+    #[command(flatten)]
+    data: DataArgs,
+    /// TOML or JSON file with `Config` fields; missing fields take their defaults.
+    /// `NEAT_<FIELD>` environment variables override the file.
+    #[arg(long)]
+    config: Option<PathBuf>,
+    /// Fitness at which training stops.
+    #[arg(long, default_value_t = 0.99)]
+    target: f32,
+    /// Maximum number of epochs.
+    #[arg(long, default_value_t = 10000)]
+    epochs: usize,
+    /// Population size.
+    #[arg(long, default_value_t = 50)]
+    population: usize,
+    /// File the best genome is written to.
+    #[arg(long, default_value = "genome.json")]
+    output: PathBuf,
+    /// Format of the written genome.
+    #[arg(long, value_enum, default_value_t = GenomeFormat::Json)]
+    format: GenomeFormat,
+    /// Optional JSONL file with per-epoch metrics.
+    #[arg(long)]
+    log: Option<PathBuf>,
+}
+
+pub fn run(args: TrainArgs) -> anyhow::Result<()> {
+    let config = match &args.config {
+        Some(path) => Config::from_file(path)?,
+        None => Config::default(),
+    }
+    .with_env()?;
+
+    let dataset = args.data.load()?;
+
+    config.validate(dataset.get_inputs().ncols(), dataset.get_targets().ncols())?;
+
+    let genome = Genome::generate_genome(
+        dataset.get_inputs().ncols(),
+        dataset.get_targets().ncols(),
+        vec![],
+        Some(Activation::Sigmoid),
+        &config,
+    )?;
+
+    let mut population: Population<Config, Organism> =
+        Population::new(args.population, config.clone());
+
+    while population.len() < args.population {
+        if let Ok(genome) = genome.mutate_connection_weight(&config) {
+            population.add_organism(Organism::new(genome));
+        }
+    }
+
+    if let Some(path) = &args.log {
+        population.add_reporter(Box::new(JsonlReporter::new(path)?));
+    }
+
+    population.calculate_fitness(&dataset);
+
+    for epoch in 0..args.epochs {
+        let fitness = population
+            .epoch(&dataset)
+            .map(|best| best.get_fitness())
+            .unwrap_or_default();
+
+        PopulationStats::new(epoch, population.get_organisms()).emit();
+
+        if fitness >= args.target {
+            info!("target fitness reached at epoch {epoch}: {fitness}");
+            break;
+        }
+    }
+
+    population.finish();
+
+    let best = population
+        .get_organisms()
+        .first()
+        .ok_or_else(|| anyhow::anyhow!("population is empty"))?;
+
+    genome_file::save(&args.output, best.get_genome(), args.format)?;
+
+    info!(
+        "best fitness {} written to {}",
+        best.get_fitness(),
+        args.output.display()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::process::{Command, Output};
+
+    fn workdir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("neat-cli-{}-{name}", std::process::id()));
+        fs::create_dir_all(&dir).expect("can't create temp dir");
+        dir
+    }
+
+    fn neat_cli(dir: &Path, args: &[&str]) -> Output {
+        Command::new(env!("CARGO_BIN_EXE_neat-cli"))
+            .current_dir(dir)
+            .args(args)
+            .output()
+            .expect("can't run neat-cli")
+    }
+
+    fn xor_csv(dir: &Path) {
+        fs::write(dir.join("xor.csv"), "a,b,out\n0,0,0\n0,1,1\n1,0,1\n1,1,0\n")
+            .expect("can't write dataset");
+    }
+
+    const DATA: [&str; 6] = ["--data", "xor.csv", "--inputs", "a,b", "--targets", "out"];
+
+    #[test]
+    fn train_convert_eval() {
+        let dir = workdir("round-trip");
+        xor_csv(&dir);
+
+        let mut train = vec![
+            "train",
+            "--epochs",
+            "5",
+            "--population",
+            "10",
+            "--output",
+            "genome.json",
+        ];
+        train.extend(DATA);
+        let output = neat_cli(&dir, &train);
+        assert!(output.status.success(), "{output:?}");
+
+        let mut evaluations = vec![];
+
+        for (file, format) in [
+            ("genome.json", None),
+            ("genome.weights", Some("weights")),
+            ("genome.bin", Some("binary")),
+        ] {
+            if let Some(format) = format {
+                let output = neat_cli(&dir, &["convert", "genome.json", file, "--to", format]);
+                assert!(output.status.success(), "{output:?}");
+            }
+
+            let mut eval = vec!["eval", "--genome", file];
+            eval.extend(DATA);
+            let output = neat_cli(&dir, &eval);
+            assert!(output.status.success(), "{output:?}");
+
+            let stdout = String::from_utf8(output.stdout).expect("stdout is not utf-8");
+            assert!(stdout.contains("mse: "));
+            evaluations.push(stdout);
+        }
+
+        assert_eq!(evaluations[0], evaluations[1]);
+        assert_eq!(evaluations[0], evaluations[2]);
+
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn eval_malformed_weights() {
+        let dir = workdir("malformed");
+        xor_csv(&dir);
+
+        for data in ["[1, 1, 2, 1, 3, 0, 0.5]", "[2, 1]", "[1, 1, 2, 1, 0, 0, 9]"] {
+            fs::write(dir.join("genome.weights"), data).expect("can't write genome");
+
+            let mut eval = vec!["eval", "--genome", "genome.weights"];
+            eval.extend(DATA);
+            let output = neat_cli(&dir, &eval);
+
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            assert!(!output.status.success());
+            assert!(!stderr.contains("panicked"), "{stderr}");
+            assert!(stderr.starts_with("Error: "), "{stderr}");
+        }
+
+        fs::remove_dir_all(dir).ok();
+    }
+}
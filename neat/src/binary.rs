@@ -0,0 +1,350 @@
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use thiserror::Error;
+
+use vivalaakam_neuro_utils::{Activation, Aggregation};
+
+use crate::connection::Connection;
+use crate::genome::{Genome, GenomeError};
+use crate::hebbian::Hebbian;
+use crate::mutation_rates::MutationRates;
+use crate::neuron_type::NeuronType;
+use crate::node::Node;
+
+/// First bytes of every binary genome.
+pub const GENOME_MAGIC: &[u8; 4] = b"NEAT";
+
+const VERSION: u8 = 1;
+
+const FLAG_RATES: u8 = 0b01;
+const FLAG_ENABLED: u8 = 0b01;
+const FLAG_PLASTIC: u8 = 0b10;
+
+#[derive(Debug, Error)]
+pub enum BinaryError {
+    #[error("Data is neither a binary genome nor legacy weights")]
+    Magic,
+    #[error("Unsupported binary genome version {0}")]
+    Version(u8),
+    #[error("Binary genome is truncated")]
+    Truncated,
+    #[error("Varint is longer than 5 bytes")]
+    Varint,
+    #[error("Checksum mismatch: expected {expected:08x}, got {got:08x}")]
+    Checksum { expected: u32, got: u32 },
+    #[error("Binary genome has {0} trailing bytes")]
+    Trailing(usize),
+    #[error(transparent)]
+    Genome(#[from] GenomeError),
+}
+
+fn put_varint(buf: &mut BytesMut, mut value: u32) {
+    while value >= 0x80 {
+        buf.put_u8((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    buf.put_u8(value as u8);
+}
+
+fn get_varint(buf: &mut &[u8]) -> Result<u32, BinaryError> {
+    let mut value = 0u32;
+
+    for shift in (0..35).step_by(7) {
+        let byte = get_u8(buf)?;
+        value |= ((byte & 0x7f) as u32) << shift;
+
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+
+    Err(BinaryError::Varint)
+}
+
+fn get_u8(buf: &mut &[u8]) -> Result<u8, BinaryError> {
+    if buf.remaining() < 1 {
+        return Err(BinaryError::Truncated);
+    }
+
+    Ok(buf.get_u8())
+}
+
+fn get_f32(buf: &mut &[u8]) -> Result<f32, BinaryError> {
+    if buf.remaining() < 4 {
+        return Err(BinaryError::Truncated);
+    }
+
+    Ok(buf.get_f32_le())
+}
+
+fn rates_to_array(rates: &MutationRates) -> [f32; 12] {
+    [
+        rates.add_node,
+        rates.add_connection,
+        rates.delete_node,
+        rates.delete_connection,
+        rates.connection_enabled,
+        rates.connection_weight_prob,
+        rates.connection_weight_delta,
+        rates.node_enabled,
+        rates.node_bias_prob,
+        rates.node_bias_delta,
+        rates.node_activation_prob,
+        rates.node_aggregation_prob,
+    ]
+}
+
+fn rates_from_array(rates: [f32; 12]) -> MutationRates {
+    MutationRates {
+        add_node: rates[0],
+        add_connection: rates[1],
+        delete_node: rates[2],
+        delete_connection: rates[3],
+        connection_enabled: rates[4],
+        connection_weight_prob: rates[5],
+        connection_weight_delta: rates[6],
+        node_enabled: rates[7],
+        node_bias_prob: rates[8],
+        node_bias_delta: rates[9],
+        node_activation_prob: rates[10],
+        node_aggregation_prob: rates[11],
+    }
+}
+
+fn get_weight(weights: &mut &[f32]) -> Result<f32, BinaryError> {
+    let (&weight, rest) = weights.split_first().ok_or(BinaryError::Truncated)?;
+    *weights = rest;
+
+    Ok(weight)
+}
+
+fn get_count(weights: &mut &[f32]) -> Result<usize, BinaryError> {
+    let count = get_weight(weights)?;
+
+    if count < 0.0 || count.fract() != 0.0 {
+        return Err(BinaryError::Magic);
+    }
+
+    Ok(count as usize)
+}
+
+/// Reads data written by `Genome::to_weights` as little-endian `f32`s.
+fn from_legacy(data: &[u8]) -> Result<Genome, BinaryError> {
+    if data.len() < 24 || !data.len().is_multiple_of(4) {
+        return Err(BinaryError::Magic);
+    }
+
+    let weights = data
+        .chunks_exact(4)
+        .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect::<Vec<_>>();
+
+    Genome::try_from_weights(&weights)
+}
+
+impl Genome {
+    /// Encodes the genome in the versioned binary format.
+    ///
+    /// Layout: `NEAT` magic, version byte, flags byte, then varint inputs, outputs,
+    /// node and connection counts. Every node is a varint id, `f32` bias, varint
+    /// position and four bytes (enabled, activation, type, aggregation); every
+    /// connection is varint from/to ids, `f32` weight, a flags byte and the five
+    /// Hebbian `f32`s when plastic. Optional mutation rates follow, and a CRC32 of
+    /// everything before it closes the data. Numbers are little-endian.
+    pub fn to_bytes(&self) -> Bytes {
+        let nodes = self.get_nodes();
+        let connections = self.get_connections();
+        let rates = self.get_rates();
+
+        let mut buf = BytesMut::with_capacity(16 + nodes.len() * 12 + connections.len() * 16);
+
+        buf.put_slice(GENOME_MAGIC);
+        buf.put_u8(VERSION);
+        buf.put_u8(if rates.is_some() { FLAG_RATES } else { 0 });
+
+        put_varint(&mut buf, self.get_inputs() as u32);
+        put_varint(&mut buf, self.get_outputs() as u32);
+        put_varint(&mut buf, nodes.len() as u32);
+        put_varint(&mut buf, connections.len() as u32);
+
+        for node in &nodes {
+            put_varint(&mut buf, node.get_id());
+            buf.put_f32_le(node.get_bias());
+            put_varint(&mut buf, node.get_position());
+            buf.put_u8(node.get_enabled() as u8);
+            buf.put_u8(node.get_activation().to_bytes());
+            buf.put_u8(node.get_type().to_bytes());
+            buf.put_u8(node.get_aggregation().to_bytes());
+        }
+
+        for connection in &connections {
+            put_varint(&mut buf, connection.get_from());
+            put_varint(&mut buf, connection.get_to());
+            buf.put_f32_le(connection.get_weight());
+
+            let hebbian = connection.get_hebbian();
+            let mut flags = 0;
+            if connection.get_enabled() {
+                flags |= FLAG_ENABLED;
+            }
+            if hebbian.is_some() {
+                flags |= FLAG_PLASTIC;
+            }
+            buf.put_u8(flags);
+
+            if let Some(hebbian) = hebbian {
+                for value in hebbian.to_weights() {
+                    buf.put_f32_le(value);
+                }
+            }
+        }
+
+        if let Some(rates) = rates {
+            for value in rates_to_array(rates) {
+                buf.put_f32_le(value);
+            }
+        }
+
+        let checksum = crc32fast::hash(&buf);
+        buf.put_u32_le(checksum);
+
+        buf.freeze()
+    }
+
+    /// Decodes a genome written by `to_weights`.
+    ///
+    /// `from_weights` panics where this returns an error: a wrong header is `Magic`, missing
+    /// values are `Truncated`, leftover values are `Trailing` (counted in bytes) and
+    /// the nodes and connections are checked by `Genome::new`.
+    pub fn try_from_weights(weights: &[f32]) -> Result<Genome, BinaryError> {
+        let mut buf = weights;
+
+        if get_weight(&mut buf)? != 1.0 || get_weight(&mut buf)? != 1.0 {
+            return Err(BinaryError::Magic);
+        }
+
+        // Inputs and outputs are derived from the node types.
+        get_count(&mut buf)?;
+        get_count(&mut buf)?;
+        let nodes_count = get_count(&mut buf)?;
+        let connections_count = get_count(&mut buf)?;
+
+        if buf.len() < (nodes_count + connections_count) * 4 {
+            return Err(BinaryError::Truncated);
+        }
+
+        let mut values = buf.iter().copied();
+
+        let nodes = (0..nodes_count)
+            .map(|_| Node::try_from_weights(&mut values))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let connections = (0..connections_count)
+            .map(|_| Connection::try_from_weights(&mut values))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let trailing = values.count();
+
+        if trailing > 0 {
+            return Err(BinaryError::Trailing(trailing * 4));
+        }
+
+        Ok(Genome::new(nodes, connections)?)
+    }
+
+    /// Decodes a genome written by `to_bytes`.
+    ///
+    /// Data without the magic header is read as legacy `to_weights` output stored
+    /// as little-endian `f32`s.
+    pub fn from_bytes(data: &[u8]) -> Result<Genome, BinaryError> {
+        if !data.starts_with(GENOME_MAGIC) {
+            return from_legacy(data);
+        }
+
+        if data.len() < GENOME_MAGIC.len() + 6 {
+            return Err(BinaryError::Truncated);
+        }
+
+        let (body, checksum) = data.split_at(data.len() - 4);
+        let expected = u32::from_le_bytes([checksum[0], checksum[1], checksum[2], checksum[3]]);
+        let got = crc32fast::hash(body);
+
+        if expected != got {
+            return Err(BinaryError::Checksum { expected, got });
+        }
+
+        let mut buf = &body[GENOME_MAGIC.len()..];
+
+        let version = get_u8(&mut buf)?;
+        if version != VERSION {
+            return Err(BinaryError::Version(version));
+        }
+
+        let flags = get_u8(&mut buf)?;
+
+        // Inputs and outputs are derived from the node types.
+        get_varint(&mut buf)?;
+        get_varint(&mut buf)?;
+        let nodes_count = get_varint(&mut buf)?;
+        let connections_count = get_varint(&mut buf)?;
+
+        let mut nodes = vec![];
+
+        for _ in 0..nodes_count {
+            let id = get_varint(&mut buf)?;
+            let bias = get_f32(&mut buf)?;
+            let position = get_varint(&mut buf)?;
+            let enabled = get_u8(&mut buf)? == 1;
+            let activation = Activation::from_bytes(get_u8(&mut buf)?);
+            let neuron_type = NeuronType::from_bytes(get_u8(&mut buf)?);
+            let aggregation = Aggregation::from_bytes(get_u8(&mut buf)?);
+
+            let mut node = Node::new(neuron_type, id, bias, Some(activation), Some(position));
+            node.set_aggregation(aggregation);
+            if !enabled {
+                node.toggle_enabled();
+            }
+            nodes.push(node);
+        }
+
+        let mut connections = vec![];
+
+        for _ in 0..connections_count {
+            let from = get_varint(&mut buf)?;
+            let to = get_varint(&mut buf)?;
+            let weight = get_f32(&mut buf)?;
+            let flags = get_u8(&mut buf)?;
+
+            let mut connection = Connection::new(from, to, weight);
+            connection.set_enabled(flags & FLAG_ENABLED != 0);
+
+            if flags & FLAG_PLASTIC != 0 {
+                let mut values = [0f32; 5];
+                for value in values.iter_mut() {
+                    *value = get_f32(&mut buf)?;
+                }
+                connection.set_hebbian(Some(Hebbian::from_weights(&mut values.into_iter())));
+            }
+
+            connections.push(connection);
+        }
+
+        let rates = if flags & FLAG_RATES != 0 {
+            let mut values = [0f32; 12];
+            for value in values.iter_mut() {
+                *value = get_f32(&mut buf)?;
+            }
+            Some(rates_from_array(values))
+        } else {
+            None
+        };
+
+        if buf.has_remaining() {
+            return Err(BinaryError::Trailing(buf.remaining()));
+        }
+
+        let mut genome = Genome::new(nodes, connections)?;
+        genome.set_rates(rates);
+
+        Ok(genome)
+    }
+}
@@ -1,5 +1,18 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use vivalaakam_neuro_utils::config_file;
+use vivalaakam_neuro_utils::ConfigError;
+
+use crate::perturbation::Perturbation;
+use crate::phase::Phase;
+
 /// Configuration parameters for NEAT evolution and mutation.
-#[derive(Clone)]
+///
+/// Missing fields take their default values when deserialized.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Config {
     /// Probability of adding a node during mutation.
     pub add_node: f32,
@@ -7,6 +20,8 @@ pub struct Config {
     pub add_connection: f32,
     /// Probability of crossover during reproduction.
     pub crossover: f32,
+    /// Probability that a matching connection disabled in either parent is disabled in the offspring.
+    pub crossover_disable: f32,
     /// Maximum number of connections allowed.
     pub connection_max: usize,
     /// Probability of enabling/disabling a connection during mutation.
@@ -15,10 +30,20 @@ pub struct Config {
     pub connection_weight: f32,
     /// Probability of mutating a connection's weight.
     pub connection_weight_prob: f32,
-    /// Maximum delta for connection weight mutation.
+    /// Maximum delta (uniform) or standard deviation (gaussian) for connection weight mutation.
     pub connection_weight_delta: f32,
     /// Number of iterations for connection weight mutation.
     pub connection_weight_iter: usize,
+    /// Distribution of the connection weight delta.
+    pub connection_weight_perturbation: Perturbation,
+    /// Probability of replacing a connection weight with a new random one instead of perturbing it.
+    pub connection_weight_replace: f32,
+    /// Mutate every enabled connection at once instead of `connection_weight_iter` random ones.
+    pub connection_weight_per_gene: bool,
+    /// Lower bound of connection weights.
+    pub connection_weight_min: f32,
+    /// Upper bound of connection weights.
+    pub connection_weight_max: f32,
     /// Maximum number of nodes allowed.
     pub node_max: usize,
     /// Initial node bias range.
@@ -27,10 +52,43 @@ pub struct Config {
     pub node_enabled: f32,
     /// Probability of mutating a node's bias.
     pub node_bias_prob: f32,
-    /// Maximum delta for node bias mutation.
+    /// Maximum delta (uniform) or standard deviation (gaussian) for node bias mutation.
     pub node_bias_delta: f32,
+    /// Distribution of the node bias delta.
+    pub node_bias_perturbation: Perturbation,
+    /// Probability of replacing a node bias with a new random one instead of perturbing it.
+    pub node_bias_replace: f32,
+    /// Lower bound of node biases.
+    pub node_bias_min: f32,
+    /// Upper bound of node biases.
+    pub node_bias_max: f32,
     /// Probability of mutating a node's activation function.
     pub node_activation_prob: f32,
+    /// Probability of mutating a node's aggregation function.
+    pub node_aggregation_prob: f32,
+    /// Probability of deleting a node during the simplifying phase.
+    pub delete_node: f32,
+    /// Probability of deleting a connection during the simplifying phase.
+    pub delete_connection: f32,
+    /// Current search phase; decides whether genomes grow or shrink.
+    pub phase: Phase,
+    /// Mean population complexity above which the simplifying phase starts.
+    pub phase_complexity_ceiling: f32,
+    /// Mean population complexity below which the complexifying phase resumes.
+    pub phase_complexity_floor: f32,
+    /// Let every genome carry and evolve its own mutation rates (see `MutationRates`);
+    /// crossover and Hebbian rates stay fixed.
+    pub self_adaptive: bool,
+    /// Learning rate of the log-normal self-adaptation of mutation rates.
+    pub self_adaptive_tau: f32,
+    /// Probability that a new connection is plastic (carries a Hebbian rule).
+    pub connection_plastic: f32,
+    /// Initial range of Hebbian coefficients and learning rate.
+    pub connection_hebbian: f32,
+    /// Probability of mutating the Hebbian rule of a plastic connection.
+    pub connection_hebbian_prob: f32,
+    /// Maximum change applied to Hebbian coefficients during mutation.
+    pub connection_hebbian_delta: f32,
 }
 
 impl Default for Config {
@@ -40,17 +98,137 @@ impl Default for Config {
             add_connection: 0.15,
             connection_enabled: 0.1,
             crossover: 0.3,
+            crossover_disable: 0.75,
             connection_weight: 1.0,
             connection_weight_prob: 0.8,
             connection_weight_delta: 0.1,
             connection_weight_iter: 5,
+            connection_weight_perturbation: Perturbation::Uniform,
+            connection_weight_replace: 0.0,
+            connection_weight_per_gene: false,
+            connection_weight_min: f32::MIN,
+            connection_weight_max: f32::MAX,
             node_bias_prob: 0.15,
             node_bias_delta: 0.1,
+            node_bias_perturbation: Perturbation::Uniform,
+            node_bias_replace: 0.0,
+            node_bias_min: f32::MIN,
+            node_bias_max: f32::MAX,
             node_bias: 1.0,
             node_activation_prob: 0.15,
+            node_aggregation_prob: 0.0,
             connection_max: 10000,
             node_max: 1000,
             node_enabled: 0.5,
+            delete_node: 0.15,
+            delete_connection: 0.15,
+            phase: Phase::Complexifying,
+            phase_complexity_ceiling: 100.0,
+            phase_complexity_floor: 50.0,
+            self_adaptive: false,
+            self_adaptive_tau: 0.2,
+            connection_plastic: 0.0,
+            connection_hebbian: 0.1,
+            connection_hebbian_prob: 0.1,
+            connection_hebbian_delta: 0.05,
+        }
+    }
+}
+
+impl Config {
+    /// Clamps a weight to `connection_weight_min..=connection_weight_max`.
+    ///
+    /// Panics when the bounds are reversed, which `validate` rejects.
+    pub fn clamp_weight(&self, weight: f32) -> f32 {
+        weight.clamp(self.connection_weight_min, self.connection_weight_max)
+    }
+
+    /// Clamps a bias to `node_bias_min..=node_bias_max`.
+    ///
+    /// Panics when the bounds are reversed, which `validate` rejects.
+    pub fn clamp_bias(&self, bias: f32) -> f32 {
+        bias.clamp(self.node_bias_min, self.node_bias_max)
+    }
+
+    /// Parses a TOML document; missing fields take their default values.
+    pub fn from_toml(data: &str) -> Result<Self, ConfigError> {
+        config_file::from_toml(data)
+    }
+
+    /// Parses a JSON document; missing fields take their default values.
+    pub fn from_json(data: &str) -> Result<Self, ConfigError> {
+        config_file::from_json(data)
+    }
+
+    /// Loads a `.toml` or `.json` file.
+    pub fn from_file<P>(path: P) -> Result<Self, ConfigError>
+    where
+        P: AsRef<Path>,
+    {
+        config_file::from_file(path.as_ref())
+    }
+
+    /// Overrides fields from `NEAT_<FIELD>` environment variables, e.g. `NEAT_ADD_NODE=0.2`.
+    pub fn with_env(self) -> Result<Self, ConfigError> {
+        config_file::with_env(self, "NEAT_")
+    }
+
+    /// Checks probabilities and bounds for a genome with the given inputs and outputs.
+    pub fn validate(&self, inputs: usize, outputs: usize) -> Result<(), ConfigError> {
+        let probabilities = [
+            ("add_node", self.add_node),
+            ("add_connection", self.add_connection),
+            ("crossover", self.crossover),
+            ("crossover_disable", self.crossover_disable),
+            ("connection_enabled", self.connection_enabled),
+            ("connection_weight_prob", self.connection_weight_prob),
+            ("connection_weight_replace", self.connection_weight_replace),
+            ("node_enabled", self.node_enabled),
+            ("node_bias_prob", self.node_bias_prob),
+            ("node_bias_replace", self.node_bias_replace),
+            ("node_activation_prob", self.node_activation_prob),
+            ("node_aggregation_prob", self.node_aggregation_prob),
+            ("delete_node", self.delete_node),
+            ("delete_connection", self.delete_connection),
+            ("connection_plastic", self.connection_plastic),
+            ("connection_hebbian_prob", self.connection_hebbian_prob),
+        ];
+
+        for (field, value) in probabilities {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(ConfigError::Probability {
+                    field: field.to_string(),
+                    value,
+                });
+            }
+        }
+
+        if self.node_max < inputs + outputs {
+            return Err(ConfigError::Invalid(format!(
+                "node_max {} is below inputs + outputs {}",
+                self.node_max,
+                inputs + outputs
+            )));
         }
+
+        if self.connection_weight_min > self.connection_weight_max {
+            return Err(ConfigError::Invalid(
+                "connection_weight_min is above connection_weight_max".to_string(),
+            ));
+        }
+
+        if self.node_bias_min > self.node_bias_max {
+            return Err(ConfigError::Invalid(
+                "node_bias_min is above node_bias_max".to_string(),
+            ));
+        }
+
+        if self.phase_complexity_floor > self.phase_complexity_ceiling {
+            return Err(ConfigError::Invalid(
+                "phase_complexity_floor is above phase_complexity_ceiling".to_string(),
+            ));
+        }
+
+        Ok(())
     }
 }
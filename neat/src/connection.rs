@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 
+use crate::binary::BinaryError;
+use crate::hebbian::Hebbian;
+
 /// Represents a connection (edge) between two nodes in the network.
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct Connection {
@@ -7,6 +10,8 @@ pub struct Connection {
     to: u32,
     weight: f32,
     enabled: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    hebbian: Option<Hebbian>,
 }
 
 impl Connection {
@@ -17,6 +22,7 @@ impl Connection {
             to,
             weight,
             enabled: true,
+            hebbian: None,
         }
     }
 
@@ -60,29 +66,63 @@ impl Connection {
         self.weight = weight
     }
 
+    /// Returns the Hebbian rule of a plastic connection.
+    pub fn get_hebbian(&self) -> Option<Hebbian> {
+        self.hebbian
+    }
+
+    /// Sets the Hebbian rule; `None` makes the connection static.
+    pub fn set_hebbian(&mut self, hebbian: Option<Hebbian>) {
+        self.hebbian = hebbian;
+    }
+
     /// Converts the connection to a vector of weights for serialization.
+    ///
+    /// Plastic connections are followed by their Hebbian coefficients.
     pub fn to_weights(&self) -> Vec<f32> {
         let enabled = if self.enabled { 1u8 } else { 0u8 };
+        let plastic = if self.hebbian.is_some() { 1u8 } else { 0u8 };
+
+        let info = f32::from_le_bytes([enabled, plastic, 0, 0]);
 
-        let info = f32::from_le_bytes([enabled, 0, 0, 0]);
+        let mut weights = vec![self.from as f32, self.to as f32, self.weight, info];
 
-        vec![self.from as f32, self.to as f32, self.weight, info]
+        if let Some(hebbian) = &self.hebbian {
+            weights.extend(hebbian.to_weights());
+        }
+
+        weights
     }
 
     /// Creates a connection from a vector of weights.
+    ///
+    /// Panics when the weights run out; see `try_from_weights`.
     pub fn from_weights(weights: &mut dyn Iterator<Item = f32>) -> Self {
-        let from = weights.next().expect("got not enough weights") as u32;
-        let to = weights.next().expect("got not enough weights") as u32;
-        let weight = weights.next().expect("got not enough weights");
-        let info = weights.next().expect("got not enough weights");
+        Connection::try_from_weights(weights).expect("got not enough weights")
+    }
 
-        let enabled = info.to_le_bytes()[0] == 1u8;
+    /// Creates a connection from a vector of weights; missing values are
+    /// `BinaryError::Truncated`.
+    pub fn try_from_weights(weights: &mut dyn Iterator<Item = f32>) -> Result<Self, BinaryError> {
+        let mut next = || weights.next().ok_or(BinaryError::Truncated);
 
-        Connection {
+        let from = next()? as u32;
+        let to = next()? as u32;
+        let weight = next()?;
+        let info = next()?.to_le_bytes();
+
+        let enabled = info[0] == 1u8;
+        let hebbian = match info[1] == 1u8 {
+            true => Some(Hebbian::try_from_weights(weights)?),
+            false => None,
+        };
+
+        Ok(Connection {
             from,
             to,
             weight,
             enabled,
-        }
+            hebbian,
+        })
     }
 }
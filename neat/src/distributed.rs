@@ -0,0 +1,565 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+#[cfg(unix)]
+use std::path::{Path, PathBuf};
+#[cfg(unix)]
+use std::sync::mpsc;
+use std::sync::{Condvar, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use thiserror::Error;
+use tracing::{debug, warn};
+
+use vivalaakam_neuro_shared::BatchEvaluatorTrait;
+
+use crate::genome::Genome;
+use crate::organism::Organism;
+
+const REQUEST: u8 = 1;
+const RESPONSE: u8 = 2;
+const FAILURE: u8 = 3;
+
+/// Largest frame payload accepted from the network.
+const MAX_PAYLOAD: u32 = 64 * 1024 * 1024;
+
+/// Pause after the first failed accept; it doubles with every failure in a row.
+const ACCEPT_BACKOFF: Duration = Duration::from_millis(10);
+
+/// Longest pause between failed accepts.
+const MAX_ACCEPT_BACKOFF: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Error)]
+pub enum DistributedError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("Protocol error: {0}")]
+    Protocol(String),
+    #[error("No live workers left with {0} genomes unevaluated")]
+    NoWorkers(usize),
+    #[error("Genome {index} failed: {message}")]
+    Evaluation { index: usize, message: String },
+}
+
+/// Genome encoding sent to workers.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum WireFormat {
+    /// `Genome::as_json`.
+    Json,
+    /// `Genome::to_bytes`.
+    #[default]
+    Binary,
+}
+
+impl WireFormat {
+    fn to_byte(self) -> u8 {
+        match self {
+            WireFormat::Json => 1,
+            WireFormat::Binary => 2,
+        }
+    }
+
+    fn encode(self, genome: &Genome) -> Vec<u8> {
+        match self {
+            WireFormat::Json => genome.as_json().into_bytes(),
+            WireFormat::Binary => genome.to_bytes().to_vec(),
+        }
+    }
+
+    fn decode(byte: u8, data: &[u8]) -> Result<Genome, DistributedError> {
+        match byte {
+            1 => serde_json::from_slice(data)
+                .map_err(|error| DistributedError::Protocol(error.to_string())),
+            2 => Genome::from_bytes(data)
+                .map_err(|error| DistributedError::Protocol(error.to_string())),
+            byte => Err(DistributedError::Protocol(format!(
+                "unknown genome format {byte}"
+            ))),
+        }
+    }
+}
+
+/// Address a `Worker` listens on.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WorkerAddress {
+    Tcp(SocketAddr),
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+impl From<SocketAddr> for WorkerAddress {
+    fn from(address: SocketAddr) -> Self {
+        WorkerAddress::Tcp(address)
+    }
+}
+
+#[cfg(unix)]
+impl From<PathBuf> for WorkerAddress {
+    fn from(path: PathBuf) -> Self {
+        WorkerAddress::Unix(path)
+    }
+}
+
+impl fmt::Display for WorkerAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WorkerAddress::Tcp(address) => write!(f, "{address}"),
+            #[cfg(unix)]
+            WorkerAddress::Unix(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+enum Listener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix(UnixListener),
+}
+
+impl Listener {
+    fn accept(&self) -> io::Result<Stream> {
+        match self {
+            Listener::Tcp(listener) => Ok(Stream::Tcp(listener.accept()?.0)),
+            #[cfg(unix)]
+            Listener::Unix(listener) => Ok(Stream::Unix(listener.accept()?.0)),
+        }
+    }
+}
+
+enum Stream {
+    Tcp(TcpStream),
+    #[cfg(unix)]
+    Unix(UnixStream),
+}
+
+impl Read for Stream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Stream::Tcp(stream) => stream.read(buf),
+            #[cfg(unix)]
+            Stream::Unix(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for Stream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Stream::Tcp(stream) => stream.write(buf),
+            #[cfg(unix)]
+            Stream::Unix(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Stream::Tcp(stream) => stream.flush(),
+            #[cfg(unix)]
+            Stream::Unix(stream) => stream.flush(),
+        }
+    }
+}
+
+/// Frame on the wire: kind byte, `u64` request id, `u32` payload length, payload.
+///
+/// Requests carry the format byte followed by the genome, responses the `f32`
+/// fitness, failures a UTF-8 message. Numbers are little-endian.
+struct Frame {
+    kind: u8,
+    id: u64,
+    payload: Vec<u8>,
+}
+
+impl Frame {
+    fn write<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let mut data = Vec::with_capacity(13 + self.payload.len());
+        data.push(self.kind);
+        data.extend(self.id.to_le_bytes());
+        data.extend((self.payload.len() as u32).to_le_bytes());
+        data.extend(&self.payload);
+
+        writer.write_all(&data)?;
+        writer.flush()
+    }
+
+    fn read<R: Read>(reader: &mut R) -> Result<Frame, DistributedError> {
+        let mut header = [0u8; 13];
+        reader.read_exact(&mut header)?;
+
+        let kind = header[0];
+        let id = u64::from_le_bytes(header[1..9].try_into().expect("8 bytes"));
+        let length = u32::from_le_bytes(header[9..13].try_into().expect("4 bytes"));
+
+        if length > MAX_PAYLOAD {
+            return Err(DistributedError::Protocol(format!(
+                "payload of {length} bytes is too large"
+            )));
+        }
+
+        let mut payload = vec![0u8; length as usize];
+        reader.read_exact(&mut payload)?;
+
+        Ok(Frame { kind, id, payload })
+    }
+}
+
+/// Serves fitness evaluations of genomes sent by a `Coordinator`.
+pub struct Worker {
+    listener: Listener,
+}
+
+impl Worker {
+    /// Binds a worker to a TCP address; port `0` picks a free port.
+    pub fn bind<A: std::net::ToSocketAddrs>(address: A) -> io::Result<Self> {
+        Ok(Worker {
+            listener: Listener::Tcp(TcpListener::bind(address)?),
+        })
+    }
+
+    /// Binds a worker to a Unix socket; the path must not exist yet.
+    #[cfg(unix)]
+    pub fn bind_unix<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Worker {
+            listener: Listener::Unix(UnixListener::bind(path)?),
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<WorkerAddress> {
+        match &self.listener {
+            Listener::Tcp(listener) => Ok(WorkerAddress::Tcp(listener.local_addr()?)),
+            #[cfg(unix)]
+            Listener::Unix(listener) => listener
+                .local_addr()?
+                .as_pathname()
+                .map(|path| WorkerAddress::Unix(path.to_path_buf()))
+                .ok_or_else(|| io::Error::other("unix socket has no path")),
+        }
+    }
+
+    /// Accepts coordinators forever, serving each connection on its own thread.
+    ///
+    /// A failed accept, e.g. an aborted connection or too many open files, is logged
+    /// and retried after a pause that grows up to a second while failures repeat.
+    pub fn run<F>(&self, evaluate: F)
+    where
+        F: Fn(&Genome) -> anyhow::Result<f32> + Sync,
+    {
+        let mut backoff = ACCEPT_BACKOFF;
+
+        thread::scope(|scope| loop {
+            let stream = match self.listener.accept() {
+                Ok(stream) => stream,
+                Err(error) => {
+                    warn!("worker failed to accept a connection: {error}");
+                    thread::sleep(backoff);
+                    backoff = (backoff * 2).min(MAX_ACCEPT_BACKOFF);
+                    continue;
+                }
+            };
+            backoff = ACCEPT_BACKOFF;
+            let evaluate = &evaluate;
+
+            scope.spawn(move || {
+                if let Err(error) = Worker::handle(stream, evaluate) {
+                    debug!("worker connection closed: {error}");
+                }
+            });
+        })
+    }
+
+    /// Answers requests on one connection until the coordinator closes it.
+    pub fn handle<S, F>(mut stream: S, evaluate: F) -> Result<(), DistributedError>
+    where
+        S: Read + Write,
+        F: Fn(&Genome) -> anyhow::Result<f32>,
+    {
+        loop {
+            let request = match Frame::read(&mut stream) {
+                Ok(frame) => frame,
+                Err(DistributedError::Io(error))
+                    if error.kind() == io::ErrorKind::UnexpectedEof =>
+                {
+                    return Ok(())
+                }
+                Err(error) => return Err(error),
+            };
+
+            if request.kind != REQUEST || request.payload.is_empty() {
+                return Err(DistributedError::Protocol(format!(
+                    "unexpected frame kind {}",
+                    request.kind
+                )));
+            }
+
+            let result = WireFormat::decode(request.payload[0], &request.payload[1..])
+                .map_err(anyhow::Error::from)
+                .and_then(|genome| evaluate(&genome));
+
+            let response = match result {
+                Ok(fitness) => Frame {
+                    kind: RESPONSE,
+                    id: request.id,
+                    payload: fitness.to_le_bytes().to_vec(),
+                },
+                Err(error) => Frame {
+                    kind: FAILURE,
+                    id: request.id,
+                    payload: error.to_string().into_bytes(),
+                },
+            };
+
+            response.write(&mut stream)?;
+        }
+    }
+}
+
+struct Job {
+    index: usize,
+    attempts: usize,
+}
+
+struct BatchState {
+    jobs: VecDeque<Job>,
+    results: Vec<Option<Result<f32, String>>>,
+}
+
+/// Shared state of one `Coordinator::evaluate` call.
+struct Batch {
+    state: Mutex<BatchState>,
+    changed: Condvar,
+}
+
+impl Batch {
+    fn new(size: usize) -> Self {
+        Batch {
+            state: Mutex::new(BatchState {
+                jobs: (0..size).map(|index| Job { index, attempts: 0 }).collect(),
+                results: vec![None; size],
+            }),
+            changed: Condvar::new(),
+        }
+    }
+
+    /// Takes the next job, waiting while jobs in flight on other workers may still
+    /// come back to the queue; `None` once every genome has a result.
+    fn next(&self) -> Option<Job> {
+        let mut state = self.state.lock().unwrap();
+
+        loop {
+            if let Some(job) = state.jobs.pop_front() {
+                return Some(job);
+            }
+
+            if state.results.iter().all(Option::is_some) {
+                return None;
+            }
+
+            state = self.changed.wait(state).unwrap();
+        }
+    }
+
+    fn finish(&self, index: usize, result: Result<f32, String>) {
+        self.state.lock().unwrap().results[index] = Some(result);
+        self.changed.notify_all();
+    }
+
+    /// Puts a failed job back in the queue, or records the failure when out of retries.
+    fn retry(&self, mut job: Job, retries: usize, message: String) {
+        job.attempts += 1;
+
+        let mut state = self.state.lock().unwrap();
+
+        if job.attempts > retries {
+            state.results[job.index] = Some(Err(message));
+        } else {
+            state.jobs.push_back(job);
+        }
+
+        drop(state);
+        self.changed.notify_all();
+    }
+
+    fn into_results(self) -> Vec<Option<Result<f32, String>>> {
+        self.state.into_inner().unwrap().results
+    }
+}
+
+/// Spreads genome evaluations over `Worker` processes.
+///
+/// Every worker gets one connection per `evaluate` call and one genome at a time.
+/// A worker that can't be reached, times out or breaks the protocol is dropped for
+/// the rest of the call and its genome goes back to the queue. A genome is tried
+/// `retries + 1` times before the call fails.
+pub struct Coordinator {
+    workers: Vec<WorkerAddress>,
+    format: WireFormat,
+    timeout: Duration,
+    retries: usize,
+}
+
+impl Coordinator {
+    /// Creates a coordinator with a 30 second timeout and 2 retries.
+    ///
+    /// Workers are TCP `SocketAddr`s or, on Unix, socket paths as `PathBuf`s.
+    pub fn new<A>(workers: impl IntoIterator<Item = A>) -> Self
+    where
+        A: Into<WorkerAddress>,
+    {
+        Coordinator {
+            workers: workers.into_iter().map(Into::into).collect(),
+            format: WireFormat::default(),
+            timeout: Duration::from_secs(30),
+            retries: 2,
+        }
+    }
+
+    pub fn with_format(mut self, format: WireFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Sets the timeout of connecting and of every evaluation.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn with_retries(mut self, retries: usize) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    pub fn get_workers(&self) -> &[WorkerAddress] {
+        &self.workers
+    }
+
+    /// Evaluates genomes on the workers; returns one fitness per genome, in order.
+    pub fn evaluate(&self, genomes: &[&Genome]) -> Result<Vec<f32>, DistributedError> {
+        if genomes.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let payloads = genomes
+            .iter()
+            .map(|genome| [vec![self.format.to_byte()], self.format.encode(genome)].concat())
+            .collect::<Vec<_>>();
+
+        let batch = Batch::new(genomes.len());
+
+        thread::scope(|scope| {
+            for address in &self.workers {
+                let (batch, payloads) = (&batch, &payloads);
+                scope.spawn(move || self.work(address, batch, payloads));
+            }
+        });
+
+        let mut fitness = vec![];
+
+        for (index, result) in batch.into_results().into_iter().enumerate() {
+            match result {
+                Some(Ok(value)) => fitness.push(value),
+                Some(Err(message)) => return Err(DistributedError::Evaluation { index, message }),
+                None => return Err(DistributedError::NoWorkers(genomes.len() - fitness.len())),
+            }
+        }
+
+        Ok(fitness)
+    }
+
+    fn connect(&self, address: &WorkerAddress) -> io::Result<Stream> {
+        match address {
+            WorkerAddress::Tcp(address) => {
+                let stream = TcpStream::connect_timeout(address, self.timeout)?;
+                stream.set_read_timeout(Some(self.timeout))?;
+                stream.set_write_timeout(Some(self.timeout))?;
+                stream.set_nodelay(true)?;
+                Ok(Stream::Tcp(stream))
+            }
+            #[cfg(unix)]
+            WorkerAddress::Unix(path) => {
+                // `UnixStream` has no `connect_timeout`; a connect stuck on a full backlog
+                // is left to finish on its own thread.
+                let (sender, receiver) = mpsc::channel();
+                let path = path.clone();
+                thread::spawn(move || sender.send(UnixStream::connect(path)));
+
+                let stream = receiver.recv_timeout(self.timeout).map_err(|_| {
+                    io::Error::new(io::ErrorKind::TimedOut, "unix socket connect timed out")
+                })??;
+                stream.set_read_timeout(Some(self.timeout))?;
+                stream.set_write_timeout(Some(self.timeout))?;
+                Ok(Stream::Unix(stream))
+            }
+        }
+    }
+
+    /// Feeds jobs to one worker until the batch is done or the worker dies.
+    fn work(&self, address: &WorkerAddress, batch: &Batch, payloads: &[Vec<u8>]) {
+        let mut stream = match self.connect(address) {
+            Ok(stream) => stream,
+            Err(error) => {
+                warn!("worker {address} is unreachable: {error}");
+                return;
+            }
+        };
+
+        while let Some(job) = batch.next() {
+            match self.request(&mut stream, job.index, &payloads[job.index]) {
+                Ok(Ok(fitness)) => batch.finish(job.index, Ok(fitness)),
+                Ok(Err(message)) => batch.retry(job, self.retries, message),
+                Err(error) => {
+                    warn!("worker {address} failed: {error}");
+                    batch.retry(job, self.retries, error.to_string());
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Sends one genome; the outer error means the worker is unusable.
+    fn request(
+        &self,
+        stream: &mut Stream,
+        index: usize,
+        payload: &[u8],
+    ) -> Result<Result<f32, String>, DistributedError> {
+        Frame {
+            kind: REQUEST,
+            id: index as u64,
+            payload: payload.to_vec(),
+        }
+        .write(stream)?;
+
+        let response = Frame::read(stream)?;
+
+        if response.id != index as u64 {
+            return Err(DistributedError::Protocol(format!(
+                "expected response {index}, got {}",
+                response.id
+            )));
+        }
+
+        match (response.kind, response.payload.as_slice()) {
+            (RESPONSE, [a, b, c, d]) => Ok(Ok(f32::from_le_bytes([*a, *b, *c, *d]))),
+            (FAILURE, message) => Ok(Err(String::from_utf8_lossy(message).into_owned())),
+            (kind, _) => Err(DistributedError::Protocol(format!(
+                "unexpected frame kind {kind}"
+            ))),
+        }
+    }
+}
+
+impl BatchEvaluatorTrait<Organism> for Coordinator {
+    fn evaluate_batch(&mut self, organisms: &[&Organism]) -> anyhow::Result<Vec<f32>> {
+        let genomes = organisms
+            .iter()
+            .map(|organism| organism.get_genome())
+            .collect::<Vec<_>>();
+
+        Ok(self.evaluate(&genomes)?)
+    }
+}
@@ -0,0 +1,442 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use tracing::debug;
+
+use crate::connection::Connection;
+use crate::genome::Genome;
+use crate::hyperneat::{SubstrateConfig, SubstrateError};
+use crate::network::Network;
+use crate::neuron_type::NeuronType;
+use crate::node::Node;
+
+/// Parameters of the ES-HyperNEAT hidden node discovery.
+#[derive(Clone)]
+pub struct EsSubstrateConfig {
+    /// Depth the quadtree is always divided to.
+    pub initial_depth: usize,
+    /// Maximal depth of the quadtree.
+    pub max_depth: usize,
+    /// Variance of a quadtree node above which it is divided further.
+    pub division_threshold: f32,
+    /// Variance of a quadtree node below which it is considered for a connection.
+    pub variance_threshold: f32,
+    /// Minimal band value a point needs to express a connection.
+    pub band_threshold: f32,
+    /// Number of times newly discovered hidden nodes are explored.
+    pub iteration_level: usize,
+    /// Weight expression and activations of the built network.
+    pub substrate: SubstrateConfig,
+}
+
+impl Default for EsSubstrateConfig {
+    fn default() -> Self {
+        EsSubstrateConfig {
+            initial_depth: 3,
+            max_depth: 5,
+            division_threshold: 0.03,
+            variance_threshold: 0.03,
+            band_threshold: 0.3,
+            iteration_level: 1,
+            substrate: SubstrateConfig::default(),
+        }
+    }
+}
+
+struct QuadPoint {
+    x: f32,
+    y: f32,
+    width: f32,
+    level: usize,
+    weight: f32,
+    children: Vec<QuadPoint>,
+}
+
+impl QuadPoint {
+    fn new(x: f32, y: f32, width: f32, level: usize) -> Self {
+        QuadPoint {
+            x,
+            y,
+            width,
+            level,
+            weight: 0.0,
+            children: vec![],
+        }
+    }
+
+    fn leaves(&self) -> Vec<f32> {
+        if self.children.is_empty() {
+            return vec![self.weight];
+        }
+
+        self.children
+            .iter()
+            .flat_map(|child| child.leaves())
+            .collect()
+    }
+
+    fn variance(&self) -> f32 {
+        if self.children.is_empty() {
+            return 0.0;
+        }
+
+        let leaves = self.leaves();
+        let mean = leaves.iter().sum::<f32>() / leaves.len() as f32;
+
+        leaves.iter().map(|w| (w - mean).powi(2)).sum::<f32>() / leaves.len() as f32
+    }
+}
+
+/// Connection discovered in the substrate, between two 2D points.
+#[derive(Debug, Clone, Copy)]
+struct PointConnection {
+    from: (f32, f32),
+    to: (f32, f32),
+    weight: f32,
+}
+
+fn key(point: (f32, f32)) -> (u32, u32) {
+    (point.0.to_bits(), point.1.to_bits())
+}
+
+/// Evolvable-substrate HyperNEAT: places hidden nodes where the CPPN pattern carries information.
+///
+/// The CPPN is a `Genome` with four inputs `(x1, y1, x2, y2)` and the weight as its
+/// first output. Hidden nodes are found by quadtree variance exploration around the
+/// inputs, then around the discovered nodes `iteration_level` times, and finally
+/// around the outputs. Nodes not on a path from an input to an output are pruned.
+///
+/// To keep the network feed-forward, hidden-to-hidden connections only go towards
+/// greater `(y, x)` coordinates.
+#[derive(Clone)]
+pub struct EsSubstrate {
+    inputs: Vec<(f32, f32)>,
+    outputs: Vec<(f32, f32)>,
+}
+
+impl EsSubstrate {
+    /// Creates a substrate from input and output node coordinates in `[-1, 1]`.
+    pub fn new(inputs: Vec<(f32, f32)>, outputs: Vec<(f32, f32)>) -> Self {
+        EsSubstrate { inputs, outputs }
+    }
+
+    /// Returns the input node coordinates.
+    pub fn get_inputs(&self) -> &[(f32, f32)] {
+        &self.inputs
+    }
+
+    /// Returns the output node coordinates.
+    pub fn get_outputs(&self) -> &[(f32, f32)] {
+        &self.outputs
+    }
+
+    fn validate(&self, cppn: &Genome) -> Result<(), SubstrateError> {
+        if self.inputs.is_empty() || self.outputs.is_empty() {
+            return Err(SubstrateError::EmptyLayer);
+        }
+
+        if cppn.get_inputs() != 4 {
+            return Err(SubstrateError::CppnInputs {
+                expected: 4,
+                got: cppn.get_inputs(),
+            });
+        }
+
+        if cppn.get_outputs() == 0 {
+            return Err(SubstrateError::CppnOutputs);
+        }
+
+        Ok(())
+    }
+
+    fn query(network: &Network, source: (f32, f32), target: (f32, f32)) -> f32 {
+        network.activate(vec![source.0, source.1, target.0, target.1])[0]
+    }
+
+    fn query_point(network: &Network, a: (f32, f32), b: (f32, f32), outgoing: bool) -> f32 {
+        if outgoing {
+            EsSubstrate::query(network, a, b)
+        } else {
+            EsSubstrate::query(network, b, a)
+        }
+    }
+
+    fn division(
+        network: &Network,
+        point: (f32, f32),
+        outgoing: bool,
+        config: &EsSubstrateConfig,
+    ) -> QuadPoint {
+        let mut root = QuadPoint::new(0.0, 0.0, 1.0, 1);
+        let mut queue: VecDeque<&mut QuadPoint> = VecDeque::new();
+        queue.push_back(&mut root);
+
+        while let Some(current) = queue.pop_front() {
+            let width = current.width / 2.0;
+
+            current.children = [(-1.0, -1.0), (1.0, -1.0), (-1.0, 1.0), (1.0, 1.0)]
+                .iter()
+                .map(|(dx, dy)| {
+                    let mut child = QuadPoint::new(
+                        current.x + dx * width,
+                        current.y + dy * width,
+                        width,
+                        current.level + 1,
+                    );
+                    child.weight =
+                        EsSubstrate::query_point(network, point, (child.x, child.y), outgoing);
+                    child
+                })
+                .collect();
+
+            if current.level < config.initial_depth
+                || (current.level < config.max_depth
+                    && current.variance() > config.division_threshold)
+            {
+                queue.extend(current.children.iter_mut());
+            }
+        }
+
+        root
+    }
+
+    fn prune_and_extract(
+        network: &Network,
+        point: (f32, f32),
+        quad: &QuadPoint,
+        outgoing: bool,
+        config: &EsSubstrateConfig,
+        connections: &mut Vec<PointConnection>,
+    ) {
+        for child in &quad.children {
+            if child.variance() >= config.variance_threshold {
+                EsSubstrate::prune_and_extract(
+                    network,
+                    point,
+                    child,
+                    outgoing,
+                    config,
+                    connections,
+                );
+                continue;
+            }
+
+            let neighbour = |dx: f32, dy: f32| {
+                let value = EsSubstrate::query_point(
+                    network,
+                    point,
+                    (child.x + dx * child.width, child.y + dy * child.width),
+                    outgoing,
+                );
+                (child.weight - value).abs()
+            };
+
+            let band = (neighbour(-1.0, 0.0).min(neighbour(1.0, 0.0)))
+                .max(neighbour(0.0, -1.0).min(neighbour(0.0, 1.0)));
+
+            if band > config.band_threshold {
+                let (from, to) = if outgoing {
+                    (point, (child.x, child.y))
+                } else {
+                    ((child.x, child.y), point)
+                };
+
+                connections.push(PointConnection {
+                    from,
+                    to,
+                    weight: child.weight,
+                });
+            }
+        }
+    }
+
+    fn explore(
+        network: &Network,
+        point: (f32, f32),
+        outgoing: bool,
+        config: &EsSubstrateConfig,
+    ) -> Vec<PointConnection> {
+        let quad = EsSubstrate::division(network, point, outgoing, config);
+        let mut connections = vec![];
+        EsSubstrate::prune_and_extract(network, point, &quad, outgoing, config, &mut connections);
+        connections
+    }
+
+    /// Discovers hidden nodes and builds a genome from the substrate.
+    pub fn build_genome(
+        &self,
+        cppn: &Genome,
+        config: &EsSubstrateConfig,
+    ) -> Result<Genome, SubstrateError> {
+        self.validate(cppn)?;
+        config.substrate.validate()?;
+
+        let network = cppn.get_network();
+
+        let fixed: HashSet<(u32, u32)> = self
+            .inputs
+            .iter()
+            .chain(self.outputs.iter())
+            .map(|point| key(*point))
+            .collect();
+
+        let mut hidden: Vec<(f32, f32)> = vec![];
+        let mut known: HashSet<(u32, u32)> = HashSet::new();
+        let mut connections: Vec<PointConnection> = vec![];
+
+        let mut discover = |found: Vec<PointConnection>,
+                            hidden: &mut Vec<(f32, f32)>,
+                            connections: &mut Vec<PointConnection>| {
+            let mut discovered = vec![];
+
+            for connection in found {
+                if fixed.contains(&key(connection.to)) {
+                    continue;
+                }
+
+                if known.insert(key(connection.to)) {
+                    hidden.push(connection.to);
+                    discovered.push(connection.to);
+                }
+
+                connections.push(connection);
+            }
+
+            discovered
+        };
+
+        let mut unexplored = vec![];
+
+        for input in &self.inputs {
+            let found = EsSubstrate::explore(&network, *input, true, config);
+            unexplored.extend(discover(found, &mut hidden, &mut connections));
+        }
+
+        for _ in 0..config.iteration_level {
+            let mut next = vec![];
+
+            for point in &unexplored {
+                let found = EsSubstrate::explore(&network, *point, true, config)
+                    .into_iter()
+                    .filter(|connection| {
+                        (connection.to.1, connection.to.0) > (connection.from.1, connection.from.0)
+                    })
+                    .collect();
+                next.extend(discover(found, &mut hidden, &mut connections));
+            }
+
+            unexplored = next;
+        }
+
+        for output in &self.outputs {
+            for connection in EsSubstrate::explore(&network, *output, false, config) {
+                if known.contains(&key(connection.from)) {
+                    connections.push(connection);
+                }
+            }
+        }
+
+        debug!(
+            "es_hyperneat discovered {} hidden nodes and {} connections",
+            hidden.len(),
+            connections.len()
+        );
+
+        let mut ids: HashMap<(u32, u32), u32> = HashMap::new();
+
+        for (i, point) in self
+            .inputs
+            .iter()
+            .chain(self.outputs.iter())
+            .chain(hidden.iter())
+            .enumerate()
+        {
+            ids.entry(key(*point)).or_insert(i as u32);
+        }
+
+        let links = connections
+            .iter()
+            .filter_map(|connection| {
+                let weight = config.substrate.express(connection.weight);
+
+                (weight != 0.0)
+                    .then(|| (ids[&key(connection.from)], ids[&key(connection.to)], weight))
+            })
+            .collect::<Vec<_>>();
+
+        let inputs = (0..self.inputs.len() as u32).collect::<Vec<_>>();
+        let outputs = (self.inputs.len() as u32..(self.inputs.len() + self.outputs.len()) as u32)
+            .collect::<Vec<_>>();
+
+        let reachable = |starts: &[u32], forward: bool| {
+            let mut viewed = starts.iter().copied().collect::<HashSet<_>>();
+            let mut queue = starts.iter().copied().collect::<VecDeque<_>>();
+
+            while let Some(current) = queue.pop_front() {
+                for (from, to, _) in &links {
+                    let (a, b) = if forward { (*from, *to) } else { (*to, *from) };
+
+                    if a == current && viewed.insert(b) {
+                        queue.push_back(b);
+                    }
+                }
+            }
+
+            viewed
+        };
+
+        let from_inputs = reachable(&inputs, true);
+        let to_outputs = reachable(&outputs, false);
+
+        let mut nodes = vec![];
+
+        for id in inputs.iter().copied() {
+            nodes.push(Node::new(NeuronType::Input, id, 0.0, None, Some(id)));
+        }
+
+        for (i, point) in hidden.iter().enumerate() {
+            let id = ids[&key(*point)];
+
+            if from_inputs.contains(&id) && to_outputs.contains(&id) {
+                nodes.push(Node::new(
+                    NeuronType::Hidden,
+                    id,
+                    0.0,
+                    Some(config.substrate.hidden_activation),
+                    Some((self.inputs.len() + i) as u32),
+                ));
+            }
+        }
+
+        for id in outputs.iter().copied() {
+            nodes.push(Node::new(
+                NeuronType::Output,
+                id,
+                0.0,
+                Some(config.substrate.output_activation),
+                Some(id),
+            ));
+        }
+
+        let kept = nodes
+            .iter()
+            .map(|node| node.get_id())
+            .collect::<HashSet<_>>();
+
+        let connections = links
+            .into_iter()
+            .filter(|(from, to, _)| kept.contains(from) && kept.contains(to))
+            .map(|(from, to, weight)| Connection::new(from, to, weight))
+            .collect();
+
+        Ok(Genome::new(nodes, connections)?)
+    }
+
+    /// Discovers hidden nodes and builds a `Network` from the substrate.
+    pub fn build_network(
+        &self,
+        cppn: &Genome,
+        config: &EsSubstrateConfig,
+    ) -> Result<Network, SubstrateError> {
+        Ok(self.build_genome(cppn, config)?.get_network())
+    }
+}
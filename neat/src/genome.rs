@@ -1,22 +1,28 @@
+use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet, VecDeque};
 
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use sha2::{Digest, Sha256};
 use thiserror::Error;
 use tracing::debug;
 
 use vivalaakam_neuro_utils::random::{
     get_random, get_random_position, get_random_range, get_random_weight,
 };
-use vivalaakam_neuro_utils::{levenshtein, Activation};
+use vivalaakam_neuro_utils::{levenshtein, Activation, Aggregation};
 
 use crate::config::Config;
 use crate::connection::Connection;
+use crate::hebbian::Hebbian;
 use crate::link::Link;
+use crate::mutation_rates::MutationRates;
 use crate::network::Network;
 use crate::neuron::Neuron;
 use crate::neuron_type::NeuronType;
 use crate::node::Node;
+use crate::phase::Phase;
+use crate::stats::GenomeStats;
 
 /// Represents a neural network genome: nodes, connections, and input/output counts.
 #[derive(Default, Clone, Serialize, Deserialize)]
@@ -25,6 +31,8 @@ pub struct Genome {
     nodes: Vec<Node>,
     inputs: u32,
     outputs: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rates: Option<MutationRates>,
 }
 
 #[derive(Debug, Error)]
@@ -51,6 +59,14 @@ pub enum GenomeError {
     NodeEnabledPositionNotFound,
     #[error("Node enabled applicant not found")]
     NodeEnabledApplicantNotFound,
+    #[error("Node aggregation applicant not found")]
+    NodeAggregationApplicantNotFound,
+    #[error("Hebbian applicant not found")]
+    HebbianApplicantNotFound,
+    #[error("Delete node applicant not found")]
+    DeleteNodeApplicantNotFound,
+    #[error("Delete connection applicant not found")]
+    DeleteConnectionApplicantNotFound,
 }
 
 impl Genome {
@@ -67,6 +83,7 @@ impl Genome {
             connections,
             inputs,
             outputs,
+            rates: None,
         };
         genome.sort_nodes()?;
 
@@ -107,17 +124,13 @@ impl Genome {
                 let node = Node::new(
                     NeuronType::Input,
                     get_random_range((inputs + outputs) as u32, u32::MAX),
-                    get_random_weight(config.node_bias),
+                    config.clamp_bias(get_random_weight(config.node_bias)),
                     activation,
                     Some(counter),
                 );
 
                 for last in &last_layer {
-                    connections.push(Connection::new(
-                        last.get_id(),
-                        node.get_id(),
-                        get_random_weight(config.connection_weight),
-                    ));
+                    connections.push(Genome::new_connection(last.get_id(), node.get_id(), config));
                 }
 
                 layer.push(node);
@@ -134,17 +147,13 @@ impl Genome {
             let node = Node::new(
                 NeuronType::Output,
                 (config.node_max - outputs + i) as u32,
-                get_random_weight(config.node_bias),
+                config.clamp_bias(get_random_weight(config.node_bias)),
                 activation,
                 Some(counter),
             );
 
             for last in &last_layer {
-                connections.push(Connection::new(
-                    last.get_id(),
-                    node.get_id(),
-                    get_random_weight(config.connection_weight),
-                ));
+                connections.push(Genome::new_connection(last.get_id(), node.get_id(), config));
             }
 
             layer.push(node);
@@ -177,6 +186,26 @@ impl Genome {
         self.connections.to_vec()
     }
 
+    /// Returns the number of input nodes.
+    pub fn get_inputs(&self) -> usize {
+        self.inputs as usize
+    }
+
+    /// Returns the number of output nodes.
+    pub fn get_outputs(&self) -> usize {
+        self.outputs as usize
+    }
+
+    /// Returns the self-adaptive mutation rates, if the genome carries them.
+    pub fn get_rates(&self) -> Option<&MutationRates> {
+        self.rates.as_ref()
+    }
+
+    /// Sets the self-adaptive mutation rates.
+    pub fn set_rates(&mut self, rates: Option<MutationRates>) {
+        self.rates = rates;
+    }
+
     fn sort_nodes(&mut self) -> Result<(), GenomeError> {
         debug!(network = json!(self).to_string(), "sort_nodes enter");
 
@@ -240,6 +269,7 @@ impl Genome {
         debug!(hidden = json!(hidden).to_string(), "sort_nodes hidden");
 
         let mut iterations = 0;
+        let max_iterations = (hidden.len() * hidden.len()).max(1000);
 
         while !hidden.is_empty() {
             let Some(current) = hidden.pop_front() else {
@@ -252,7 +282,7 @@ impl Genome {
 
             iterations += 1;
 
-            if iterations > 1000 {
+            if iterations > max_iterations {
                 return Err(GenomeError::SortNodesStacked);
             }
 
@@ -334,6 +364,7 @@ impl Genome {
                         node.get_position(),
                         conn.get_weight(),
                     )
+                    .with_hebbian(conn.get_hebbian())
                 })
                 .collect::<Vec<_>>();
 
@@ -344,32 +375,84 @@ impl Genome {
     }
 
     /// Mutates the genome (add node, add connection, weights, etc).
-    pub fn mutate(&self, child: Option<&Genome>, config: &Config) -> Result<Self, GenomeError> {
+    ///
+    /// `child` is the crossover partner together with the result of comparing
+    /// the fitness of `self` to it. Its mutation rates are recombined only when the
+    /// `crossover` roll succeeds.
+    pub fn mutate(
+        &self,
+        child: Option<(&Genome, Ordering)>,
+        config: &Config,
+    ) -> Result<Self, GenomeError> {
         let mut genome = Genome::default();
         self.clone_into(&mut genome);
 
         debug!("mutate enter: {}", json!(genome));
 
-        if let Some(child) = child {
-            if get_random() < config.crossover {
-                if let Ok(g) = genome.mutate_crossover(child) {
-                    genome = g;
-                    debug!(genome = json!(genome).to_string(), "mutate crossover");
-                }
-            }
-        }
+        let child = child.filter(|_| get_random() < config.crossover);
+
+        let rates = if config.self_adaptive {
+            let rates = self
+                .rates
+                .clone()
+                .unwrap_or_else(|| MutationRates::from(config));
 
-        if get_random() < config.add_node {
-            if let Ok(g) = genome.mutate_add_node(config) {
+            let rates = match child.and_then(|(child, _)| child.rates.as_ref()) {
+                Some(other) => rates.crossover(other),
+                None => rates,
+            };
+
+            Some(rates.mutate(config.self_adaptive_tau))
+        } else {
+            self.rates.clone()
+        };
+
+        let adapted = rates
+            .as_ref()
+            .filter(|_| config.self_adaptive)
+            .map(|rates| rates.apply(config));
+        let config = adapted.as_ref().unwrap_or(config);
+
+        if let Some((child, fitness)) = child {
+            if let Ok(g) = genome.mutate_crossover(child, fitness, config) {
                 genome = g;
-                debug!(genome = json!(genome).to_string(), "mutate add_node");
+                debug!(genome = json!(genome).to_string(), "mutate crossover");
             }
         }
 
-        if get_random() < config.add_connection {
-            if let Ok(g) = genome.mutate_add_connection(config) {
-                genome = g;
-                debug!(genome = json!(genome).to_string(), "mutate add_connection");
+        match config.phase {
+            Phase::Complexifying => {
+                if get_random() < config.add_node {
+                    if let Ok(g) = genome.mutate_add_node(config) {
+                        genome = g;
+                        debug!(genome = json!(genome).to_string(), "mutate add_node");
+                    }
+                }
+
+                if get_random() < config.add_connection {
+                    if let Ok(g) = genome.mutate_add_connection(config) {
+                        genome = g;
+                        debug!(genome = json!(genome).to_string(), "mutate add_connection");
+                    }
+                }
+            }
+            Phase::Simplifying => {
+                if get_random() < config.delete_node {
+                    if let Ok(g) = genome.mutate_delete_node() {
+                        genome = g;
+                        debug!(genome = json!(genome).to_string(), "mutate delete_node");
+                    }
+                }
+
+                if get_random() < config.delete_connection {
+                    if let Ok(g) = genome.mutate_delete_connection() {
+                        genome = g;
+                        debug!(
+                            genome = json!(genome).to_string(),
+                            "mutate delete_connection"
+                        );
+                    }
+                }
             }
         }
 
@@ -383,16 +466,26 @@ impl Genome {
             }
         }
         if get_random() < config.connection_weight_prob {
-            let retry = get_random_range(1, config.connection_weight_iter);
-
-            for i in 0..retry {
-                if let Ok(g) = genome.mutate_connection_weight(config) {
+            if config.connection_weight_per_gene {
+                if let Ok(g) = genome.mutate_connection_weights(config) {
                     genome = g;
                     debug!(
                         genome = json!(genome).to_string(),
-                        "mutate connection_weight ({i})"
+                        "mutate connection_weights"
                     );
                 }
+            } else {
+                let retry = get_random_range(1, config.connection_weight_iter);
+
+                for i in 0..retry {
+                    if let Ok(g) = genome.mutate_connection_weight(config) {
+                        genome = g;
+                        debug!(
+                            genome = json!(genome).to_string(),
+                            "mutate connection_weight ({i})"
+                        );
+                    }
+                }
             }
         }
 
@@ -417,6 +510,28 @@ impl Genome {
             }
         }
 
+        if get_random() < config.node_aggregation_prob {
+            if let Ok(g) = genome.mutate_node_aggregation(config) {
+                genome = g;
+                debug!(
+                    genome = json!(genome).to_string(),
+                    "mutate node_aggregation"
+                );
+            }
+        }
+
+        if get_random() < config.connection_hebbian_prob {
+            if let Ok(g) = genome.mutate_connection_hebbian(config) {
+                genome = g;
+                debug!(
+                    genome = json!(genome).to_string(),
+                    "mutate connection_hebbian"
+                );
+            }
+        }
+
+        genome.rates = rates;
+
         Ok(genome)
     }
 
@@ -461,11 +576,15 @@ impl Genome {
         let node = Node::new(
             NeuronType::Hidden,
             node_id,
-            get_random_weight(config.node_bias),
+            config.clamp_bias(get_random_weight(config.node_bias)),
             Some(activations[activation]),
             None,
         );
-        let from = Connection::new(connection.get_from(), node.get_id(), 1.0);
+        let from = Connection::new(
+            connection.get_from(),
+            node.get_id(),
+            config.clamp_weight(1.0),
+        );
         genome.connections.push(from);
 
         let to = Connection::new(node.get_id(), connection.get_to(), connection.get_weight());
@@ -525,16 +644,95 @@ impl Genome {
 
             let applicant = applicants.get(conn).unwrap();
 
-            genome.add_connection(Connection::new(
-                applicant.0,
-                applicant.1,
-                get_random_weight(config.connection_weight),
-            ));
+            genome.add_connection(Genome::new_connection(applicant.0, applicant.1, config));
         }
 
         Ok(genome)
     }
 
+    /// Mutates the genome by deleting a hidden node and every connection attached to it.
+    ///
+    /// Nodes whose removal cuts an output off from every input are never deleted.
+    pub fn mutate_delete_node(&self) -> Result<Self, GenomeError> {
+        let reachable = self.count_reachable_outputs();
+        let mut applicants = self.get_hidden_node_ids();
+
+        while !applicants.is_empty() {
+            let applicant = applicants.swap_remove(get_random_position(applicants.len()));
+
+            let nodes = self
+                .nodes
+                .iter()
+                .filter(|node| node.get_id() != applicant)
+                .cloned()
+                .collect::<Vec<_>>();
+
+            let connections = self
+                .connections
+                .iter()
+                .filter(|connection| {
+                    connection.get_from() != applicant && connection.get_to() != applicant
+                })
+                .cloned()
+                .collect::<Vec<_>>();
+
+            let genome = Genome::new(nodes, connections)?;
+
+            if genome.count_reachable_outputs() == reachable {
+                return Ok(genome);
+            }
+        }
+
+        Err(GenomeError::DeleteNodeApplicantNotFound)
+    }
+
+    /// Mutates the genome by deleting a connection.
+    ///
+    /// Connections whose removal cuts an output off from every input are never deleted.
+    pub fn mutate_delete_connection(&self) -> Result<Self, GenomeError> {
+        let reachable = self.count_reachable_outputs();
+        let mut applicants = (0..self.connections.len()).collect::<Vec<_>>();
+
+        while !applicants.is_empty() {
+            let applicant = applicants.swap_remove(get_random_position(applicants.len()));
+
+            let mut genome = self.clone();
+            genome.connections.remove(applicant);
+
+            if genome.count_reachable_outputs() == reachable {
+                genome.sort_nodes()?;
+                return Ok(genome);
+            }
+        }
+
+        Err(GenomeError::DeleteConnectionApplicantNotFound)
+    }
+
+    /// Returns the number of outputs reachable from an input over enabled connections.
+    fn count_reachable_outputs(&self) -> usize {
+        let mut viewed = self
+            .get_input_node_ids()
+            .into_iter()
+            .collect::<HashSet<_>>();
+        let mut queue = viewed.iter().copied().collect::<VecDeque<_>>();
+
+        while let Some(current) = queue.pop_front() {
+            for connection in &self.connections {
+                if connection.get_enabled()
+                    && connection.get_from() == current
+                    && viewed.insert(connection.get_to())
+                {
+                    queue.push_back(connection.get_to());
+                }
+            }
+        }
+
+        self.nodes
+            .iter()
+            .filter(|node| node.get_type() == NeuronType::Output && viewed.contains(&node.get_id()))
+            .count()
+    }
+
     /// Mutates the bias of a node.
     pub fn mutate_node_bias(&self, config: &Config) -> Result<Self, GenomeError> {
         let mut genome = Genome::default();
@@ -551,7 +749,7 @@ impl Genome {
         };
 
         if let Some(node) = genome.nodes.get_mut(index) {
-            node.set_bias(node.get_bias() + get_random_weight(config.node_bias_delta));
+            node.set_bias(Genome::mutate_bias(node.get_bias(), config));
         }
         Ok(genome)
     }
@@ -580,6 +778,68 @@ impl Genome {
         Ok(genome)
     }
 
+    /// Mutates the aggregation function of a hidden or output node.
+    pub fn mutate_node_aggregation(&self, _config: &Config) -> Result<Self, GenomeError> {
+        let mut genome = self.clone();
+
+        let applicants = self
+            .nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| node.get_type() != NeuronType::Input)
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+
+        if applicants.is_empty() {
+            return Err(GenomeError::NodeAggregationApplicantNotFound);
+        }
+
+        let index = applicants[get_random_position(applicants.len())];
+
+        let aggregations = Aggregation::to_vec();
+        let aggregation = get_random_position(aggregations.len());
+        genome.nodes[index].set_aggregation(aggregations[aggregation]);
+
+        Ok(genome)
+    }
+
+    /// Perturbs the Hebbian rule of a random plastic connection.
+    pub fn mutate_connection_hebbian(&self, config: &Config) -> Result<Self, GenomeError> {
+        let mut genome = self.clone();
+
+        let applicants = self
+            .connections
+            .iter()
+            .enumerate()
+            .filter(|(_, connection)| connection.get_hebbian().is_some())
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+
+        if applicants.is_empty() {
+            return Err(GenomeError::HebbianApplicantNotFound);
+        }
+
+        let index = applicants[get_random_position(applicants.len())];
+        let hebbian = genome.connections[index]
+            .get_hebbian()
+            .map(|hebbian| hebbian.mutate(config.connection_hebbian_delta));
+        genome.connections[index].set_hebbian(hebbian);
+
+        Ok(genome)
+    }
+
+    /// Creates a connection with a random weight, plastic with `connection_plastic` probability.
+    fn new_connection(from: u32, to: u32, config: &Config) -> Connection {
+        let weight = config.clamp_weight(get_random_weight(config.connection_weight));
+        let mut connection = Connection::new(from, to, weight);
+
+        if get_random() < config.connection_plastic {
+            connection.set_hebbian(Some(Hebbian::random(config.connection_hebbian)));
+        }
+
+        connection
+    }
+
     /// Toggles the enabled/disabled state of a node.
     pub fn mutate_node_enabled(&self, _config: &Config) -> Result<Self, GenomeError> {
         let mut genome = Genome::default();
@@ -623,14 +883,48 @@ impl Genome {
         };
 
         if let Some(connection) = genome.connections.get_mut(index) {
-            connection.set_weight(
-                connection.get_weight() + get_random_weight(config.connection_weight_delta),
-            );
+            connection.set_weight(Genome::mutate_weight(connection.get_weight(), config));
         }
 
         Ok(genome)
     }
 
+    /// Mutates the weight of every enabled connection.
+    pub fn mutate_connection_weights(&self, config: &Config) -> Result<Self, GenomeError> {
+        let mut genome = self.clone();
+
+        for connection in genome.connections.iter_mut() {
+            if connection.get_enabled() {
+                connection.set_weight(Genome::mutate_weight(connection.get_weight(), config));
+            }
+        }
+
+        Ok(genome)
+    }
+
+    fn mutate_weight(weight: f32, config: &Config) -> f32 {
+        let weight = if get_random() < config.connection_weight_replace {
+            get_random_weight(config.connection_weight)
+        } else {
+            weight
+                + config
+                    .connection_weight_perturbation
+                    .sample(config.connection_weight_delta)
+        };
+
+        config.clamp_weight(weight)
+    }
+
+    fn mutate_bias(bias: f32, config: &Config) -> f32 {
+        let bias = if get_random() < config.node_bias_replace {
+            get_random_weight(config.node_bias)
+        } else {
+            bias + config.node_bias_perturbation.sample(config.node_bias_delta)
+        };
+
+        config.clamp_bias(bias)
+    }
+
     /// Toggles the enabled/disabled state of a connection.
     pub fn mutate_connection_enabled(&self) -> Result<Self, GenomeError> {
         let mut genome = self.clone();
@@ -659,31 +953,113 @@ impl Genome {
         Ok(genome)
     }
 
-    /// Performs crossover with another genome.
-    pub fn mutate_crossover(&self, child: &Genome) -> Result<Self, GenomeError> {
-        let mut nodes = self.get_nodes();
-        let mut connections = self.get_connections();
+    /// Performs canonical NEAT crossover with another genome.
+    ///
+    /// Genes are aligned by id. Matching genes are inherited from a random parent,
+    /// disjoint and excess genes from the fitter one; `fitness` compares `self` to
+    /// `child`. On equal fitness disjoint and excess genes are inherited from
+    /// either parent at random.
+    pub fn mutate_crossover(
+        &self,
+        child: &Genome,
+        fitness: Ordering,
+        config: &Config,
+    ) -> Result<Self, GenomeError> {
+        let (fitter, other) = match fitness {
+            Ordering::Less => (child, self),
+            _ => (self, child),
+        };
+        let equal = fitness == Ordering::Equal;
+
+        let other_nodes: HashMap<u32, &Node> = other
+            .nodes
+            .iter()
+            .map(|node| (node.get_id(), node))
+            .collect();
+
+        let mut nodes = vec![];
+
+        for node in &fitter.nodes {
+            match other_nodes.get(&node.get_id()) {
+                Some(matching) if matching.get_type() == node.get_type() => {
+                    if get_random() < 0.5 {
+                        nodes.push(node.clone());
+                    } else {
+                        nodes.push((*matching).clone());
+                    }
+                }
+                _ => {
+                    if node.get_type() != NeuronType::Hidden || !equal || get_random() < 0.5 {
+                        nodes.push(node.clone());
+                    }
+                }
+            }
+        }
+
+        if equal {
+            let fitter_nodes: HashSet<u32> = HashSet::from_iter(fitter.get_all_node_ids());
+
+            for node in &other.nodes {
+                if node.get_type() == NeuronType::Hidden
+                    && !fitter_nodes.contains(&node.get_id())
+                    && get_random() < 0.5
+                {
+                    nodes.push(node.clone());
+                }
+            }
+        }
+
+        let other_connections: HashMap<String, &Connection> = other
+            .connections
+            .iter()
+            .map(|connection| (connection.get_id(), connection))
+            .collect();
+
+        let mut connections = vec![];
 
-        let exists_nodes: HashSet<u32> = HashSet::from_iter(self.get_hidden_node_ids());
+        for connection in &fitter.connections {
+            match other_connections.get(&connection.get_id()) {
+                Some(matching) => {
+                    let mut gene = if get_random() < 0.5 {
+                        connection.clone()
+                    } else {
+                        (*matching).clone()
+                    };
+
+                    if !connection.get_enabled() || !matching.get_enabled() {
+                        gene.set_enabled(get_random() >= config.crossover_disable);
+                    }
 
-        for node in child.get_nodes() {
-            if node.get_type() == NeuronType::Hidden && !exists_nodes.contains(&node.get_id()) {
-                nodes.push(node.clone());
+                    connections.push(gene);
+                }
+                None => {
+                    if !equal || get_random() < 0.5 {
+                        connections.push(connection.clone());
+                    }
+                }
             }
         }
 
-        let exists_connections: HashSet<String> = HashSet::from_iter(
-            self.get_connections()
+        if equal {
+            let fitter_connections: HashSet<String> = fitter
+                .connections
                 .iter()
-                .map(|connection| connection.get_id()),
-        );
+                .map(|connection| connection.get_id())
+                .collect();
 
-        for connection in &child.get_connections() {
-            if !exists_connections.contains(&connection.get_id()) {
-                connections.push(connection.clone());
+            for connection in &other.connections {
+                if !fitter_connections.contains(&connection.get_id()) && get_random() < 0.5 {
+                    connections.push(connection.clone());
+                }
             }
         }
 
+        let inherited: HashSet<u32> = nodes.iter().map(|node| node.get_id()).collect();
+
+        connections.retain(|connection| {
+            inherited.contains(&connection.get_from()) && inherited.contains(&connection.get_to())
+        });
+
         debug!("mutate_crossover: nodes {nodes:?}");
         debug!("mutate_crossover: connections {connections:?}");
 
@@ -702,6 +1078,129 @@ impl Genome {
         levenshtein(parent_nodes, child_nodes).unwrap_or(i32::MAX)
     }
 
+    /// Returns node and connection counts, depth, activation histogram and weight range.
+    pub fn stats(&self) -> GenomeStats {
+        let mut stats = GenomeStats::default();
+        let mut depths: HashMap<u32, usize> = HashMap::new();
+        let mut enabled = HashSet::new();
+
+        for node in &self.nodes {
+            if node.get_enabled() {
+                stats.nodes_enabled += 1;
+                enabled.insert(node.get_id());
+
+                if node.get_type() != NeuronType::Input {
+                    *stats
+                        .activations
+                        .entry(format!("{:?}", node.get_activation()))
+                        .or_default() += 1;
+                }
+            } else {
+                stats.nodes_disabled += 1;
+            }
+
+            if node.get_type() == NeuronType::Hidden {
+                stats.hidden += 1;
+            }
+        }
+
+        let mut weights = vec![];
+
+        for connection in &self.connections {
+            if connection.get_enabled() {
+                stats.connections_enabled += 1;
+                weights.push(connection.get_weight());
+            } else {
+                stats.connections_disabled += 1;
+            }
+        }
+
+        if !weights.is_empty() {
+            stats.weight_min = weights.iter().copied().fold(f32::MAX, f32::min);
+            stats.weight_max = weights.iter().copied().fold(f32::MIN, f32::max);
+            stats.weight_mean = weights.iter().sum::<f32>() / weights.len() as f32;
+        }
+
+        let mut nodes = self.nodes.iter().collect::<Vec<_>>();
+        nodes.sort_by_key(|node| node.get_position());
+
+        for node in nodes {
+            let depth = self
+                .connections
+                .iter()
+                .filter(|connection| {
+                    connection.get_enabled()
+                        && connection.get_to() == node.get_id()
+                        && enabled.contains(&connection.get_from())
+                })
+                .filter_map(|connection| depths.get(&connection.get_from()))
+                .map(|depth| depth + 1)
+                .max()
+                .unwrap_or_default();
+
+            depths.insert(node.get_id(), depth);
+            stats.depth = stats.depth.max(depth);
+        }
+
+        stats
+    }
+
+    /// Renders the genome as a Graphviz DOT graph; disabled genes are dashed.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph genome {\n    rankdir=LR;\n");
+
+        let mut nodes = self.nodes.iter().collect::<Vec<_>>();
+        nodes.sort_by_key(|node| node.get_position());
+
+        for node in nodes {
+            let (shape, label) = match node.get_type() {
+                NeuronType::Input => ("box", format!("{}\\nInput", node.get_id())),
+                neuron_type => (
+                    if neuron_type == NeuronType::Output {
+                        "doublecircle"
+                    } else {
+                        "circle"
+                    },
+                    format!(
+                        "{}\\n{:?}\\nb={:.3}",
+                        node.get_id(),
+                        node.get_activation(),
+                        node.get_bias()
+                    ),
+                ),
+            };
+
+            let style = if node.get_enabled() {
+                "solid"
+            } else {
+                "dashed"
+            };
+
+            dot.push_str(&format!(
+                "    n{} [label=\"{label}\", shape={shape}, style={style}];\n",
+                node.get_id()
+            ));
+        }
+
+        for connection in &self.connections {
+            let style = if connection.get_enabled() {
+                "solid"
+            } else {
+                "dashed"
+            };
+
+            dot.push_str(&format!(
+                "    n{} -> n{} [label=\"{:.3}\", style={style}];\n",
+                connection.get_from(),
+                connection.get_to(),
+                connection.get_weight()
+            ));
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
     /// Serializes the genome to JSON.
     pub fn as_json(&self) -> String {
         json!(self).to_string()
@@ -717,6 +1216,11 @@ impl Genome {
             .position(|connection| connection.get_id() == id)
     }
 
+    /// Returns the number of hidden nodes and connections, used to schedule search phases.
+    pub fn get_complexity(&self) -> usize {
+        self.get_hidden_node_ids().len() + self.connections.len()
+    }
+
     pub fn get_topology(&self) -> Vec<usize> {
         vec![
             1,
@@ -728,6 +1232,77 @@ impl Genome {
         ]
     }
 
+    /// Returns a SHA-256 of the structure: node ids and types, connection endpoints and
+    /// enabled flags. Insensitive to the order of nodes and connections.
+    pub fn get_topology_hash(&self) -> String {
+        self.hash(false)
+    }
+
+    /// Returns a SHA-256 of everything that affects the network: the structure plus
+    /// biases, activations, aggregations, weights and Hebbian rules. Insensitive to the
+    /// order of nodes and connections; mutation rates and positions are not included.
+    pub fn get_hash(&self) -> String {
+        self.hash(true)
+    }
+
+    fn hash(&self, full: bool) -> String {
+        // Each node and connection becomes a byte record; sorting the whole records keeps
+        // the hash independent of order even for duplicate ids or edges.
+        let mut nodes = self
+            .nodes
+            .iter()
+            .map(|node| {
+                let mut record = node.get_id().to_le_bytes().to_vec();
+                record.extend([node.get_type().to_bytes(), node.get_enabled() as u8]);
+
+                if full {
+                    record.extend([
+                        node.get_activation().to_bytes(),
+                        node.get_aggregation().to_bytes(),
+                    ]);
+                    record.extend(node.get_bias().to_le_bytes());
+                }
+
+                record
+            })
+            .collect::<Vec<_>>();
+        nodes.sort();
+
+        let mut connections = self
+            .connections
+            .iter()
+            .map(|connection| {
+                let mut record = connection.get_from().to_le_bytes().to_vec();
+                record.extend(connection.get_to().to_le_bytes());
+                record.push(connection.get_enabled() as u8);
+
+                if full {
+                    record.extend(connection.get_weight().to_le_bytes());
+                    record.push(connection.get_hebbian().is_some() as u8);
+
+                    if let Some(hebbian) = connection.get_hebbian() {
+                        for value in hebbian.to_weights() {
+                            record.extend(value.to_le_bytes());
+                        }
+                    }
+                }
+
+                record
+            })
+            .collect::<Vec<_>>();
+        connections.sort();
+
+        let mut hasher = Sha256::new();
+        hasher.update(self.inputs.to_le_bytes());
+        hasher.update(self.outputs.to_le_bytes());
+
+        for record in nodes.iter().chain(&connections) {
+            hasher.update(record);
+        }
+
+        format!("{:x}", hasher.finalize())
+    }
+
     /// Converts the genome to a flat vector of weights.
     pub fn to_weights(&self) -> Vec<f32> {
         self.get_topology()
@@ -743,39 +1318,12 @@ impl Genome {
     }
 
     /// Creates a genome from a flat vector of weights.
+    ///
+    /// Panics on malformed weights; use `try_from_weights` for untrusted data.
     pub fn from_weights(weights: impl IntoIterator<Item = f32>) -> Self {
-        let mut weights = weights.into_iter();
+        let weights = weights.into_iter().collect::<Vec<_>>();
 
-        let network_type = weights.next().expect("got no network type") as usize;
-        assert_eq!(network_type, 1);
-
-        let network_version = weights.next().expect("got no network version") as usize;
-        assert_eq!(network_version, 1);
-
-        let inputs = weights.next().expect("got no inputs") as u32;
-        let outputs = weights.next().expect("got no outputs") as u32;
-
-        let nodes_count = weights.next().expect("got no nodes count") as usize;
-        let connections_count = weights.next().expect("got no connections count") as usize;
-
-        let nodes = (0..nodes_count)
-            .map(|_| Node::from_weights(&mut weights))
-            .collect::<Vec<_>>();
-
-        let connections = (0..connections_count)
-            .map(|_| Connection::from_weights(&mut weights))
-            .collect::<Vec<_>>();
-
-        if weights.next().is_some() {
-            panic!("got too many weights");
-        }
-
-        Genome {
-            nodes,
-            connections,
-            inputs,
-            outputs,
-        }
+        Genome::try_from_weights(&weights).expect("got malformed weights")
     }
 
     pub fn get_all_node_ids(&self) -> Vec<u32> {
@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+use vivalaakam_neuro_utils::random::{get_random_range, get_random_weight};
+
+use crate::binary::BinaryError;
+
+/// Evolvable Hebbian rule of a plastic connection.
+///
+/// During activation the weight changes by
+/// `learning_rate * (a * pre * post + b * pre + c * post + d)`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Hebbian {
+    pub a: f32,
+    pub b: f32,
+    pub c: f32,
+    pub d: f32,
+    pub learning_rate: f32,
+}
+
+impl Hebbian {
+    /// Creates a rule with coefficients in `[-range, range)` and a learning rate in `[0, range)`.
+    ///
+    /// A `range` of zero or less gives the inactive rule with every value zero.
+    pub fn random(range: f32) -> Self {
+        if range <= 0.0 {
+            return Hebbian {
+                a: 0.0,
+                b: 0.0,
+                c: 0.0,
+                d: 0.0,
+                learning_rate: 0.0,
+            };
+        }
+
+        Hebbian {
+            a: get_random_weight(range),
+            b: get_random_weight(range),
+            c: get_random_weight(range),
+            d: get_random_weight(range),
+            learning_rate: get_random_range(0.0, range),
+        }
+    }
+
+    /// Returns the weight change for the given pre- and post-synaptic activations.
+    pub fn delta(&self, pre: f32, post: f32) -> f32 {
+        self.learning_rate * (self.a * pre * post + self.b * pre + self.c * post + self.d)
+    }
+
+    /// Returns a copy with every coefficient perturbed by up to `delta`.
+    pub fn mutate(&self, delta: f32) -> Self {
+        if delta <= 0.0 {
+            return *self;
+        }
+
+        Hebbian {
+            a: self.a + get_random_weight(delta),
+            b: self.b + get_random_weight(delta),
+            c: self.c + get_random_weight(delta),
+            d: self.d + get_random_weight(delta),
+            learning_rate: (self.learning_rate + get_random_weight(delta)).max(0.0),
+        }
+    }
+
+    /// Converts the rule to a vector of weights for serialization.
+    pub fn to_weights(&self) -> Vec<f32> {
+        vec![self.a, self.b, self.c, self.d, self.learning_rate]
+    }
+
+    /// Creates a rule from a vector of weights.
+    ///
+    /// Panics when the weights run out; see `try_from_weights`.
+    pub fn from_weights(weights: &mut dyn Iterator<Item = f32>) -> Self {
+        Hebbian::try_from_weights(weights).expect("got not enough weights")
+    }
+
+    /// Creates a rule from a vector of weights; missing values are `BinaryError::Truncated`.
+    pub fn try_from_weights(weights: &mut dyn Iterator<Item = f32>) -> Result<Self, BinaryError> {
+        let mut next = || weights.next().ok_or(BinaryError::Truncated);
+
+        Ok(Hebbian {
+            a: next()?,
+            b: next()?,
+            c: next()?,
+            d: next()?,
+            learning_rate: next()?,
+        })
+    }
+}
@@ -0,0 +1,309 @@
+use ndarray::Array2;
+use thiserror::Error;
+use tracing::debug;
+
+use vivalaakam_neuro_nn::{Layer, NeuralNetwork, Neuron as LayerNeuron};
+use vivalaakam_neuro_utils::Activation;
+
+use crate::connection::Connection;
+use crate::genome::{Genome, GenomeError};
+use crate::network::Network;
+use crate::neuron_type::NeuronType;
+use crate::node::Node;
+
+#[derive(Debug, Error)]
+pub enum SubstrateError {
+    #[error("Substrate layer is empty")]
+    EmptyLayer,
+    #[error("Substrate coordinates have different dimensions")]
+    DimensionMismatch,
+    #[error("CPPN expects {expected} inputs, got {got}")]
+    CppnInputs { expected: usize, got: usize },
+    #[error("CPPN has no outputs")]
+    CppnOutputs,
+    #[error("Weight threshold {0} is outside [0, 1)")]
+    Threshold(f32),
+    #[error(transparent)]
+    Genome(#[from] GenomeError),
+}
+
+/// Weights (`targets x sources`) and target biases between two consecutive substrate layers.
+#[derive(Debug, Clone)]
+pub struct SubstrateWeights {
+    pub weights: Array2<f32>,
+    pub biases: Vec<f32>,
+}
+
+/// Parameters turning CPPN outputs into substrate weights.
+#[derive(Clone)]
+pub struct SubstrateConfig {
+    /// Minimal absolute CPPN output that expresses a connection.
+    pub weight_threshold: f32,
+    /// Magnitude of an expressed weight for a CPPN output of 1.
+    pub weight_max: f32,
+    /// Activation of hidden substrate nodes.
+    pub hidden_activation: Activation,
+    /// Activation of output substrate nodes.
+    pub output_activation: Activation,
+}
+
+impl Default for SubstrateConfig {
+    fn default() -> Self {
+        SubstrateConfig {
+            weight_threshold: 0.2,
+            weight_max: 3.0,
+            hidden_activation: Activation::Sigmoid,
+            output_activation: Activation::Sigmoid,
+        }
+    }
+}
+
+impl SubstrateConfig {
+    /// Checks that `weight_threshold` is in `[0, 1)`.
+    pub fn validate(&self) -> Result<(), SubstrateError> {
+        if !(0.0..1.0).contains(&self.weight_threshold) {
+            return Err(SubstrateError::Threshold(self.weight_threshold));
+        }
+
+        Ok(())
+    }
+
+    /// Scales a CPPN output into a weight, returning zero below the threshold.
+    pub fn express(&self, value: f32) -> f32 {
+        if value.abs() <= self.weight_threshold {
+            return 0.0;
+        }
+
+        value.signum() * (value.abs() - self.weight_threshold) / (1.0 - self.weight_threshold)
+            * self.weight_max
+    }
+}
+
+/// Layered HyperNEAT substrate: node coordinates of the input, hidden and output layers.
+///
+/// A `Genome` is interpreted as a CPPN taking the coordinates of the source and
+/// target nodes (`2 * dimensions` inputs). Its first output is the connection
+/// weight, the optional second output is the bias of the target node, queried
+/// with the source coordinates set to zero.
+#[derive(Clone)]
+pub struct Substrate {
+    layers: Vec<Vec<Vec<f32>>>,
+}
+
+impl Substrate {
+    /// Creates a substrate without hidden layers.
+    pub fn new(inputs: Vec<Vec<f32>>, outputs: Vec<Vec<f32>>) -> Self {
+        Substrate {
+            layers: vec![inputs, outputs],
+        }
+    }
+
+    /// Appends a hidden layer placed before the output layer.
+    pub fn with_hidden_layer(mut self, layer: Vec<Vec<f32>>) -> Self {
+        let position = self.layers.len() - 1;
+        self.layers.insert(position, layer);
+        self
+    }
+
+    /// Returns the coordinates of every layer, from inputs to outputs.
+    pub fn get_layers(&self) -> &[Vec<Vec<f32>>] {
+        &self.layers
+    }
+
+    /// Returns the dimensions of the substrate coordinates.
+    pub fn get_dimensions(&self) -> usize {
+        self.layers
+            .first()
+            .and_then(|layer| layer.first())
+            .map(|point| point.len())
+            .unwrap_or_default()
+    }
+
+    fn validate(&self, cppn: &Genome) -> Result<(), SubstrateError> {
+        let dimensions = self.get_dimensions();
+
+        for layer in &self.layers {
+            if layer.is_empty() {
+                return Err(SubstrateError::EmptyLayer);
+            }
+
+            if layer.iter().any(|point| point.len() != dimensions) {
+                return Err(SubstrateError::DimensionMismatch);
+            }
+        }
+
+        if cppn.get_inputs() != dimensions * 2 {
+            return Err(SubstrateError::CppnInputs {
+                expected: dimensions * 2,
+                got: cppn.get_inputs(),
+            });
+        }
+
+        if cppn.get_outputs() == 0 {
+            return Err(SubstrateError::CppnOutputs);
+        }
+
+        Ok(())
+    }
+
+    /// Queries the CPPN for every pair of consecutive layers.
+    pub fn query(
+        &self,
+        cppn: &Genome,
+        config: &SubstrateConfig,
+    ) -> Result<Vec<SubstrateWeights>, SubstrateError> {
+        self.validate(cppn)?;
+        config.validate()?;
+
+        let network = cppn.get_network();
+        let dimensions = self.get_dimensions();
+
+        let result = self
+            .layers
+            .windows(2)
+            .map(|pair| {
+                let (sources, targets) = (&pair[0], &pair[1]);
+
+                let rows = targets
+                    .iter()
+                    .flat_map(|target| {
+                        sources
+                            .iter()
+                            .flat_map(move |source| [source.as_slice(), target.as_slice()].concat())
+                    })
+                    .collect::<Vec<_>>();
+
+                let matrix =
+                    Array2::from_shape_vec((targets.len() * sources.len(), dimensions * 2), rows)
+                        .expect("substrate query shape");
+
+                let outputs = network.activate_matrix(&matrix);
+
+                let weights = Array2::from_shape_fn((targets.len(), sources.len()), |(t, s)| {
+                    config.express(outputs[[t * sources.len() + s, 0]])
+                });
+
+                let biases = if cppn.get_outputs() > 1 {
+                    let rows = targets
+                        .iter()
+                        .flat_map(|target| [vec![0f32; dimensions], target.to_vec()].concat())
+                        .collect::<Vec<_>>();
+
+                    let matrix = Array2::from_shape_vec((targets.len(), dimensions * 2), rows)
+                        .expect("substrate query shape");
+
+                    network
+                        .activate_matrix(&matrix)
+                        .column(1)
+                        .iter()
+                        .map(|value| value * config.weight_max)
+                        .collect()
+                } else {
+                    vec![0f32; targets.len()]
+                };
+
+                debug!("substrate query weights: {weights:?}, biases: {biases:?}");
+
+                SubstrateWeights { weights, biases }
+            })
+            .collect();
+
+        Ok(result)
+    }
+
+    /// Builds a genome with a node per substrate point and a connection per expressed weight.
+    pub fn build_genome(
+        &self,
+        cppn: &Genome,
+        config: &SubstrateConfig,
+    ) -> Result<Genome, SubstrateError> {
+        let query = self.query(cppn, config)?;
+
+        let mut nodes = vec![];
+        let mut connections = vec![];
+        let mut offset = 0u32;
+
+        for i in 0..self.layers[0].len() as u32 {
+            nodes.push(Node::new(NeuronType::Input, i, 0.0, None, Some(i)));
+        }
+
+        for (layer, SubstrateWeights { weights, biases }) in query.iter().enumerate() {
+            let sources = self.layers[layer].len() as u32;
+            let output = layer == query.len() - 1;
+
+            for (t, bias) in biases.iter().enumerate() {
+                let id = offset + sources + t as u32;
+
+                nodes.push(Node::new(
+                    if output {
+                        NeuronType::Output
+                    } else {
+                        NeuronType::Hidden
+                    },
+                    id,
+                    *bias,
+                    Some(if output {
+                        config.output_activation
+                    } else {
+                        config.hidden_activation
+                    }),
+                    Some(id),
+                ));
+
+                for s in 0..sources {
+                    let weight = weights[[t, s as usize]];
+
+                    if weight != 0.0 {
+                        connections.push(Connection::new(offset + s, id, weight));
+                    }
+                }
+            }
+
+            offset += sources;
+        }
+
+        Ok(Genome::new(nodes, connections)?)
+    }
+
+    /// Builds a `Network` from the substrate.
+    pub fn build_network(
+        &self,
+        cppn: &Genome,
+        config: &SubstrateConfig,
+    ) -> Result<Network, SubstrateError> {
+        Ok(self.build_genome(cppn, config)?.get_network())
+    }
+
+    /// Builds a layered `NeuralNetwork` from the substrate; unexpressed weights are zero.
+    pub fn build_neural_network(
+        &self,
+        cppn: &Genome,
+        config: &SubstrateConfig,
+    ) -> Result<NeuralNetwork, SubstrateError> {
+        let query = self.query(cppn, config)?;
+        let last = query.len() - 1;
+
+        let layers = query
+            .into_iter()
+            .enumerate()
+            .map(|(i, SubstrateWeights { weights, biases })| {
+                let activation = if i == last {
+                    config.output_activation
+                } else {
+                    config.hidden_activation
+                };
+
+                let neurons = weights
+                    .rows()
+                    .into_iter()
+                    .zip(biases)
+                    .map(|(row, bias)| LayerNeuron::new(bias, activation, row.to_vec()))
+                    .collect();
+
+                Layer::new(neurons, activation)
+            })
+            .collect();
+
+        Ok(NeuralNetwork::new(layers))
+    }
+}
@@ -1,16 +1,39 @@
+pub use binary::{BinaryError, GENOME_MAGIC};
 pub use config::Config;
 pub use connection::Connection;
-pub use genome::Genome;
+pub use distributed::{Coordinator, DistributedError, WireFormat, Worker, WorkerAddress};
+pub use es_hyperneat::{EsSubstrate, EsSubstrateConfig};
+pub use genome::{Genome, GenomeError};
+pub use hebbian::Hebbian;
+pub use hyperneat::{Substrate, SubstrateConfig, SubstrateError, SubstrateWeights};
+pub use mutation_rates::MutationRates;
+pub use neat_python::{NeatPythonConfig, NeatPythonError};
+pub use network::Network;
 pub use neuron_type::NeuronType;
 pub use node::Node;
 pub use organism::Organism;
+pub use perturbation::Perturbation;
+pub use phase::Phase;
+pub use snapshot_reporter::SnapshotReporter;
+pub use stats::{GenomeStats, PopulationStats};
 
+mod binary;
 mod config;
 mod connection;
+mod distributed;
+mod es_hyperneat;
 mod genome;
+mod hebbian;
+mod hyperneat;
 mod link;
+mod mutation_rates;
+mod neat_python;
 mod network;
 mod neuron;
 mod neuron_type;
 mod node;
 mod organism;
+mod perturbation;
+mod phase;
+mod snapshot_reporter;
+mod stats;
@@ -1,9 +1,12 @@
+use crate::hebbian::Hebbian;
+
 #[derive(Clone)]
 /// Represents a weighted connection (link) between two nodes.
 pub struct Link {
     weight: f32,
     from_id: u32,
     to_id: u32,
+    hebbian: Option<Hebbian>,
 }
 
 impl Link {
@@ -13,9 +16,20 @@ impl Link {
             from_id,
             to_id,
             weight,
+            hebbian: None,
         }
     }
 
+    /// Returns the link with the given Hebbian rule.
+    pub fn with_hebbian(self, hebbian: Option<Hebbian>) -> Self {
+        Link { hebbian, ..self }
+    }
+
+    /// Returns the Hebbian rule of a plastic link.
+    pub fn get_hebbian(&self) -> Option<Hebbian> {
+        self.hebbian
+    }
+
     /// Returns the weight of the link.
     pub fn get_weight(&self) -> f32 {
         self.weight
@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+use vivalaakam_neuro_utils::random::get_random_gaussian;
+
+use crate::config::Config;
+
+/// Mutation probabilities and step sizes carried by a self-adaptive genome.
+///
+/// `crossover`, `crossover_disable`, `connection_plastic` and the Hebbian rates are
+/// not carried: they always come from the `Config`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MutationRates {
+    pub add_node: f32,
+    pub add_connection: f32,
+    pub delete_node: f32,
+    pub delete_connection: f32,
+    pub connection_enabled: f32,
+    pub connection_weight_prob: f32,
+    pub connection_weight_delta: f32,
+    pub node_enabled: f32,
+    pub node_bias_prob: f32,
+    pub node_bias_delta: f32,
+    pub node_activation_prob: f32,
+    pub node_aggregation_prob: f32,
+}
+
+impl From<&Config> for MutationRates {
+    fn from(config: &Config) -> Self {
+        MutationRates {
+            add_node: config.add_node,
+            add_connection: config.add_connection,
+            delete_node: config.delete_node,
+            delete_connection: config.delete_connection,
+            connection_enabled: config.connection_enabled,
+            connection_weight_prob: config.connection_weight_prob,
+            connection_weight_delta: config.connection_weight_delta,
+            node_enabled: config.node_enabled,
+            node_bias_prob: config.node_bias_prob,
+            node_bias_delta: config.node_bias_delta,
+            node_activation_prob: config.node_activation_prob,
+            node_aggregation_prob: config.node_aggregation_prob,
+        }
+    }
+}
+
+impl MutationRates {
+    /// Returns a copy with every rate multiplied by `exp(tau * N(0, 1))`.
+    ///
+    /// Probabilities are clamped to `[0, 1]`.
+    pub fn mutate(&self, tau: f32) -> Self {
+        let probability = |rate: f32| (rate * get_random_gaussian(tau).exp()).clamp(0.0, 1.0);
+        let step = |rate: f32| rate * get_random_gaussian(tau).exp();
+
+        MutationRates {
+            add_node: probability(self.add_node),
+            add_connection: probability(self.add_connection),
+            delete_node: probability(self.delete_node),
+            delete_connection: probability(self.delete_connection),
+            connection_enabled: probability(self.connection_enabled),
+            connection_weight_prob: probability(self.connection_weight_prob),
+            connection_weight_delta: step(self.connection_weight_delta),
+            node_enabled: probability(self.node_enabled),
+            node_bias_prob: probability(self.node_bias_prob),
+            node_bias_delta: step(self.node_bias_delta),
+            node_activation_prob: probability(self.node_activation_prob),
+            node_aggregation_prob: probability(self.node_aggregation_prob),
+        }
+    }
+
+    /// Recombines two sets of rates by averaging them.
+    pub fn crossover(&self, other: &MutationRates) -> Self {
+        MutationRates {
+            add_node: (self.add_node + other.add_node) / 2.0,
+            add_connection: (self.add_connection + other.add_connection) / 2.0,
+            delete_node: (self.delete_node + other.delete_node) / 2.0,
+            delete_connection: (self.delete_connection + other.delete_connection) / 2.0,
+            connection_enabled: (self.connection_enabled + other.connection_enabled) / 2.0,
+            connection_weight_prob: (self.connection_weight_prob + other.connection_weight_prob)
+                / 2.0,
+            connection_weight_delta: (self.connection_weight_delta + other.connection_weight_delta)
+                / 2.0,
+            node_enabled: (self.node_enabled + other.node_enabled) / 2.0,
+            node_bias_prob: (self.node_bias_prob + other.node_bias_prob) / 2.0,
+            node_bias_delta: (self.node_bias_delta + other.node_bias_delta) / 2.0,
+            node_activation_prob: (self.node_activation_prob + other.node_activation_prob) / 2.0,
+            node_aggregation_prob: (self.node_aggregation_prob + other.node_aggregation_prob) / 2.0,
+        }
+    }
+
+    /// Returns a copy of `config` with its rates replaced by these ones.
+    pub fn apply(&self, config: &Config) -> Config {
+        Config {
+            add_node: self.add_node,
+            add_connection: self.add_connection,
+            delete_node: self.delete_node,
+            delete_connection: self.delete_connection,
+            connection_enabled: self.connection_enabled,
+            connection_weight_prob: self.connection_weight_prob,
+            connection_weight_delta: self.connection_weight_delta,
+            node_enabled: self.node_enabled,
+            node_bias_prob: self.node_bias_prob,
+            node_bias_delta: self.node_bias_delta,
+            node_activation_prob: self.node_activation_prob,
+            node_aggregation_prob: self.node_aggregation_prob,
+            ..config.clone()
+        }
+    }
+}
@@ -0,0 +1,624 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use vivalaakam_neuro_utils::{Activation, Aggregation};
+
+use crate::config::Config;
+use crate::connection::Connection;
+use crate::genome::{Genome, GenomeError};
+use crate::neuron_type::NeuronType;
+use crate::node::Node;
+use crate::perturbation::Perturbation;
+
+const NEAT: &str = "NEAT";
+const GENOME: &str = "DefaultGenome";
+
+#[derive(Debug, Error)]
+pub enum NeatPythonError {
+    #[error("Invalid INI syntax at line {0}")]
+    Syntax(usize),
+    #[error("Missing key `{key}` in section [{section}]")]
+    MissingKey { section: String, key: String },
+    #[error("Invalid value `{value}` for key `{key}`")]
+    InvalidValue { key: String, value: String },
+    #[error("Activation `{0}` has no counterpart")]
+    UnsupportedActivation(String),
+    #[error("Aggregation `{0}` has no counterpart")]
+    UnsupportedAggregation(String),
+    #[error("Node {0} is not defined")]
+    UnknownNode(i64),
+    #[error("Plastic connections can't be exported")]
+    Plastic,
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Genome(#[from] GenomeError),
+}
+
+type Field = fn(&mut Config) -> &mut f32;
+
+/// `[DefaultGenome]` keys copied as is into `Config` fields.
+///
+/// `weight_init_stdev` and `bias_init_stdev` are only approximate: neat-python draws
+/// initial values from a Gaussian with that deviation, `Config` from a uniform range
+/// `[-value, value]`.
+const FIELDS: [(&str, Field); 19] = [
+    ("node_add_prob", |c| &mut c.add_node),
+    ("node_delete_prob", |c| &mut c.delete_node),
+    ("conn_add_prob", |c| &mut c.add_connection),
+    ("conn_delete_prob", |c| &mut c.delete_connection),
+    ("enabled_mutate_rate", |c| &mut c.connection_enabled),
+    ("weight_init_stdev", |c| &mut c.connection_weight),
+    ("weight_mutate_rate", |c| &mut c.connection_weight_prob),
+    ("weight_mutate_power", |c| &mut c.connection_weight_delta),
+    ("weight_replace_rate", |c| &mut c.connection_weight_replace),
+    ("weight_min_value", |c| &mut c.connection_weight_min),
+    ("weight_max_value", |c| &mut c.connection_weight_max),
+    ("bias_init_stdev", |c| &mut c.node_bias),
+    ("bias_mutate_rate", |c| &mut c.node_bias_prob),
+    ("bias_mutate_power", |c| &mut c.node_bias_delta),
+    ("bias_replace_rate", |c| &mut c.node_bias_replace),
+    ("bias_min_value", |c| &mut c.node_bias_min),
+    ("bias_max_value", |c| &mut c.node_bias_max),
+    ("activation_mutate_rate", |c| &mut c.node_activation_prob),
+    ("aggregation_mutate_rate", |c| &mut c.node_aggregation_prob),
+];
+
+/// Activations with a neat-python counterpart, with the factor their input is scaled by.
+///
+/// neat-python steepens some functions (`sigmoid(5z)`, `tanh(2.5z)`, `sin(5z)`,
+/// `exp(-5z²)`); the factor maps its input `z` onto the input of our activation.
+const ACTIVATIONS: [(&str, Activation, f32); 7] = [
+    ("sigmoid", Activation::Sigmoid, 5.0 / 4.924273),
+    ("tanh", Activation::Tanh, 2.5),
+    ("sin", Activation::Sinusoid, 5.0),
+    ("gauss", Activation::Gaussian, 2.236068),
+    ("relu", Activation::Relu, 1.0),
+    ("selu", Activation::Selu, 1.0),
+    ("identity", Activation::Identity, 1.0),
+];
+
+const AGGREGATIONS: [(&str, Aggregation); 7] = [
+    ("sum", Aggregation::Sum),
+    ("product", Aggregation::Product),
+    ("max", Aggregation::Max),
+    ("min", Aggregation::Min),
+    ("maxabs", Aggregation::MaxAbs),
+    ("median", Aggregation::Median),
+    ("mean", Aggregation::Mean),
+];
+
+fn activation_from_name(name: &str) -> Result<(Activation, f32), NeatPythonError> {
+    ACTIVATIONS
+        .iter()
+        .find(|(n, _, _)| *n == name)
+        .map(|(_, activation, scale)| (*activation, *scale))
+        .ok_or_else(|| NeatPythonError::UnsupportedActivation(name.to_string()))
+}
+
+fn activation_to_name(activation: Activation) -> Result<(&'static str, f32), NeatPythonError> {
+    ACTIVATIONS
+        .iter()
+        .find(|(_, a, _)| a.to_bytes() == activation.to_bytes())
+        .map(|(name, _, scale)| (*name, *scale))
+        .ok_or_else(|| NeatPythonError::UnsupportedActivation(format!("{activation:?}")))
+}
+
+fn aggregation_from_name(name: &str) -> Result<Aggregation, NeatPythonError> {
+    AGGREGATIONS
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, aggregation)| *aggregation)
+        .ok_or_else(|| NeatPythonError::UnsupportedAggregation(name.to_string()))
+}
+
+fn aggregation_to_name(aggregation: Aggregation) -> &'static str {
+    AGGREGATIONS
+        .iter()
+        .find(|(_, a)| a.to_bytes() == aggregation.to_bytes())
+        .map(|(name, _)| *name)
+        .unwrap_or("sum")
+}
+
+/// Sections of a `configparser` file; keys are lowercased like Python does.
+struct Ini(HashMap<String, HashMap<String, String>>);
+
+impl Ini {
+    fn parse(text: &str) -> Result<Self, NeatPythonError> {
+        let mut sections: HashMap<String, HashMap<String, String>> = HashMap::new();
+        let mut section: Option<String> = None;
+        let mut last: Option<String> = None;
+
+        for (number, line) in text.lines().enumerate() {
+            let trimmed = line.trim();
+
+            if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with(';') {
+                continue;
+            }
+
+            if let Some(name) = trimmed
+                .strip_prefix('[')
+                .and_then(|rest| rest.strip_suffix(']'))
+            {
+                section = Some(name.trim().to_string());
+                sections.entry(name.trim().to_string()).or_default();
+                last = None;
+                continue;
+            }
+
+            let Some(current) = section.as_ref() else {
+                return Err(NeatPythonError::Syntax(number + 1));
+            };
+            let values = sections.entry(current.clone()).or_default();
+
+            if line.starts_with(char::is_whitespace) {
+                if let Some(value) = last.as_ref().and_then(|key| values.get_mut(key)) {
+                    value.push(' ');
+                    value.push_str(trimmed);
+                    continue;
+                }
+            }
+
+            let Some((key, value)) = trimmed.split_once(['=', ':']) else {
+                return Err(NeatPythonError::Syntax(number + 1));
+            };
+
+            let key = key.trim().to_lowercase();
+            values.insert(key.clone(), value.trim().to_string());
+            last = Some(key);
+        }
+
+        Ok(Ini(sections))
+    }
+
+    fn get(&self, section: &str, key: &str) -> Option<&str> {
+        self.0
+            .get(section)
+            .and_then(|values| values.get(key))
+            .map(String::as_str)
+    }
+
+    fn value<T: FromStr>(&self, section: &str, key: &str) -> Result<Option<T>, NeatPythonError> {
+        self.get(section, key)
+            .map(|value| {
+                value.parse().map_err(|_| NeatPythonError::InvalidValue {
+                    key: key.to_string(),
+                    value: value.to_string(),
+                })
+            })
+            .transpose()
+    }
+
+    fn required<T: FromStr>(&self, section: &str, key: &str) -> Result<T, NeatPythonError> {
+        self.value(section, key)?
+            .ok_or_else(|| NeatPythonError::MissingKey {
+                section: section.to_string(),
+                key: key.to_string(),
+            })
+    }
+}
+
+/// A neat-python configuration file mapped onto `Config`.
+///
+/// `[DefaultGenome]` rates and bounds are copied into `Config` as is; both
+/// perturbations become `Gaussian`, as in neat-python. Keys without a counterpart
+/// (speciation, stagnation, response mutation) are ignored on import and written
+/// with neat-python's defaults on export. Initial weight and bias deviations become
+/// the bounds of uniform ranges, which is an approximation.
+#[derive(Debug, Clone)]
+pub struct NeatPythonConfig {
+    /// Mutation parameters.
+    pub config: Config,
+    /// Number of genome inputs (`num_inputs`).
+    pub inputs: usize,
+    /// Number of genome outputs (`num_outputs`).
+    pub outputs: usize,
+    /// Number of initial hidden nodes (`num_hidden`).
+    pub hidden: usize,
+    /// Population size (`pop_size`).
+    pub population: usize,
+    /// Fitness that stops the run (`fitness_threshold`).
+    pub fitness_threshold: f32,
+    /// Activation of new nodes (`activation_default`).
+    pub activation: Activation,
+    /// Aggregation of new nodes (`aggregation_default`).
+    pub aggregation: Aggregation,
+}
+
+impl NeatPythonConfig {
+    /// Creates a neat-python config with a population of 150 and no hidden nodes.
+    pub fn new(config: Config, inputs: usize, outputs: usize) -> Self {
+        NeatPythonConfig {
+            config,
+            inputs,
+            outputs,
+            hidden: 0,
+            population: 150,
+            fitness_threshold: 1.0,
+            activation: Activation::Sigmoid,
+            aggregation: Aggregation::Sum,
+        }
+    }
+
+    /// Parses a neat-python INI configuration.
+    pub fn from_ini(text: &str) -> Result<Self, NeatPythonError> {
+        let ini = Ini::parse(text)?;
+
+        let mut config = Config {
+            connection_weight_perturbation: Perturbation::Gaussian,
+            node_bias_perturbation: Perturbation::Gaussian,
+            ..Config::default()
+        };
+
+        for (key, field) in FIELDS {
+            if let Some(value) = ini.value(GENOME, key)? {
+                *field(&mut config) = value;
+            }
+        }
+
+        let activation = match ini.get(GENOME, "activation_default") {
+            Some(name) => activation_from_name(name)?.0,
+            None => Activation::Sigmoid,
+        };
+
+        let aggregation = match ini.get(GENOME, "aggregation_default") {
+            Some(name) => aggregation_from_name(name)?,
+            None => Aggregation::Sum,
+        };
+
+        Ok(NeatPythonConfig {
+            config,
+            inputs: ini.required(GENOME, "num_inputs")?,
+            outputs: ini.required(GENOME, "num_outputs")?,
+            hidden: ini.value(GENOME, "num_hidden")?.unwrap_or_default(),
+            population: ini.required(NEAT, "pop_size")?,
+            fitness_threshold: ini.value(NEAT, "fitness_threshold")?.unwrap_or(1.0),
+            activation,
+            aggregation,
+        })
+    }
+
+    /// Writes a complete neat-python INI configuration.
+    pub fn to_ini(&self) -> Result<String, NeatPythonError> {
+        let (activation, _) = activation_to_name(self.activation)?;
+        let activation_options = if self.config.node_activation_prob > 0.0 {
+            ACTIVATIONS
+                .iter()
+                .map(|(name, _, _)| *name)
+                .collect::<Vec<_>>()
+                .join(" ")
+        } else {
+            activation.to_string()
+        };
+        let aggregation = aggregation_to_name(self.aggregation);
+        let aggregation_options = if self.config.node_aggregation_prob > 0.0 {
+            AGGREGATIONS
+                .iter()
+                .map(|(name, _)| *name)
+                .collect::<Vec<_>>()
+                .join(" ")
+        } else {
+            aggregation.to_string()
+        };
+
+        let mut config = self.config.clone();
+        let mut out = String::new();
+
+        let _ = writeln!(out, "[{NEAT}]");
+        let _ = writeln!(out, "fitness_criterion = max");
+        let _ = writeln!(out, "fitness_threshold = {}", self.fitness_threshold);
+        let _ = writeln!(out, "pop_size = {}", self.population);
+        let _ = writeln!(out, "reset_on_extinction = False");
+        let _ = writeln!(out);
+
+        let _ = writeln!(out, "[{GENOME}]");
+        let _ = writeln!(out, "num_inputs = {}", self.inputs);
+        let _ = writeln!(out, "num_outputs = {}", self.outputs);
+        let _ = writeln!(out, "num_hidden = {}", self.hidden);
+        let _ = writeln!(out, "feed_forward = True");
+        let _ = writeln!(out, "initial_connection = full");
+        let _ = writeln!(out, "activation_default = {activation}");
+        let _ = writeln!(out, "activation_options = {activation_options}");
+        let _ = writeln!(out, "aggregation_default = {aggregation}");
+        let _ = writeln!(out, "aggregation_options = {aggregation_options}");
+        for (key, field) in FIELDS {
+            let _ = writeln!(out, "{key} = {}", field(&mut config));
+        }
+        let _ = writeln!(out, "bias_init_mean = 0.0");
+        let _ = writeln!(out, "weight_init_mean = 0.0");
+        let _ = writeln!(out, "enabled_default = True");
+        let _ = writeln!(out, "response_init_mean = 1.0");
+        let _ = writeln!(out, "response_init_stdev = 0.0");
+        let _ = writeln!(out, "response_min_value = -30.0");
+        let _ = writeln!(out, "response_max_value = 30.0");
+        let _ = writeln!(out, "response_mutate_power = 0.0");
+        let _ = writeln!(out, "response_mutate_rate = 0.0");
+        let _ = writeln!(out, "response_replace_rate = 0.0");
+        let _ = writeln!(out, "compatibility_disjoint_coefficient = 1.0");
+        let _ = writeln!(out, "compatibility_weight_coefficient = 0.5");
+        let _ = writeln!(out);
+
+        let _ = writeln!(out, "[DefaultSpeciesSet]");
+        let _ = writeln!(out, "compatibility_threshold = 3.0");
+        let _ = writeln!(out);
+
+        let _ = writeln!(out, "[DefaultStagnation]");
+        let _ = writeln!(out, "species_fitness_func = max");
+        let _ = writeln!(out, "max_stagnation = 20");
+        let _ = writeln!(out, "species_elitism = 2");
+        let _ = writeln!(out);
+
+        let _ = writeln!(out, "[DefaultReproduction]");
+        let _ = writeln!(out, "elitism = 2");
+        let _ = writeln!(out, "survival_threshold = 0.2");
+
+        Ok(out)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct PythonNode {
+    key: i64,
+    bias: f32,
+    #[serde(default = "default_response")]
+    response: f32,
+    activation: String,
+    aggregation: String,
+}
+
+fn default_response() -> f32 {
+    1.0
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Serialize, Deserialize)]
+struct PythonConnection {
+    key: (i64, i64),
+    weight: f32,
+    #[serde(default = "default_enabled")]
+    enabled: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PythonGenome {
+    #[serde(default)]
+    key: i64,
+    #[serde(default)]
+    fitness: Option<f32>,
+    nodes: Vec<PythonNode>,
+    connections: Vec<PythonConnection>,
+}
+
+/// Returns the index of the connection of `node` whose weight carries the scale of a
+/// product aggregation: the first enabled incoming one.
+fn product_carrier(connections: &[(u32, u32, bool)], node: u32) -> Option<usize> {
+    connections
+        .iter()
+        .position(|(_, to, enabled)| *to == node && *enabled)
+}
+
+impl Genome {
+    /// Imports a genome dumped from neat-python as JSON.
+    ///
+    /// Pickled genomes (neat-python's own checkpoint format) are not supported; dump the
+    /// genome's `nodes` and `connections` to JSON in Python first.
+    ///
+    /// Input keys `-1..=-inputs` become ids `0..inputs`, every other key `k` becomes
+    /// `k + inputs`; keys below `outputs` are outputs. The activation steepness and the
+    /// node `response` are folded into the bias and incoming weights, so the network
+    /// computes the same values as neat-python's `FeedForwardNetwork`.
+    pub fn from_neat_python(
+        json: &str,
+        inputs: usize,
+        outputs: usize,
+    ) -> Result<Genome, NeatPythonError> {
+        let genome: PythonGenome = serde_json::from_str(json)?;
+
+        let id = |key: i64| -> Result<u32, NeatPythonError> {
+            match key {
+                key if key < 0 && -key as usize <= inputs => Ok((-key - 1) as u32),
+                key if key >= 0 => Ok(key as u32 + inputs as u32),
+                key => Err(NeatPythonError::UnknownNode(key)),
+            }
+        };
+
+        let mut nodes = (0..inputs as u32)
+            .map(|i| Node::new(NeuronType::Input, i, 0.0, None, Some(i)))
+            .collect::<Vec<_>>();
+        let mut factors = HashMap::new();
+
+        for node in &genome.nodes {
+            let (activation, scale) = activation_from_name(&node.activation)?;
+            let mut aggregation = aggregation_from_name(&node.aggregation)?;
+            let factor = scale * node.response;
+
+            if factor < 0.0 {
+                aggregation = match aggregation {
+                    Aggregation::Max => Aggregation::Min,
+                    Aggregation::Min => Aggregation::Max,
+                    aggregation => aggregation,
+                };
+            }
+
+            if node.key < 0 {
+                return Err(NeatPythonError::UnknownNode(node.key));
+            }
+
+            let id = id(node.key)?;
+            let neuron_type = if node.key < outputs as i64 {
+                NeuronType::Output
+            } else {
+                NeuronType::Hidden
+            };
+
+            let mut imported = Node::new(
+                neuron_type,
+                id,
+                node.bias * scale,
+                Some(activation),
+                Some(id),
+            );
+            imported.set_aggregation(aggregation);
+            nodes.push(imported);
+            factors.insert(id, (factor, aggregation));
+        }
+
+        let mut links = vec![];
+
+        for connection in &genome.connections {
+            let (from, to) = (id(connection.key.0)?, id(connection.key.1)?);
+
+            if from >= inputs as u32 && !factors.contains_key(&from) {
+                return Err(NeatPythonError::UnknownNode(connection.key.0));
+            }
+
+            if !factors.contains_key(&to) {
+                return Err(NeatPythonError::UnknownNode(connection.key.1));
+            }
+
+            links.push((from, to, connection.enabled));
+        }
+
+        let connections = genome
+            .connections
+            .iter()
+            .zip(&links)
+            .enumerate()
+            .map(|(i, (connection, (from, to, enabled)))| {
+                let (factor, aggregation) = factors[to];
+                let weight = match aggregation {
+                    Aggregation::Product if product_carrier(&links, *to) != Some(i) => {
+                        connection.weight
+                    }
+                    _ => connection.weight * factor,
+                };
+
+                let mut imported = Connection::new(*from, *to, weight);
+                imported.set_enabled(*enabled);
+                imported
+            })
+            .collect();
+
+        Ok(Genome::new(nodes, connections)?)
+    }
+
+    /// Exports the genome in the JSON layout read by `from_neat_python`.
+    ///
+    /// Inputs, outputs and hidden nodes are numbered in network order; every node
+    /// gets a `response` of 1. Fails for activations neat-python lacks and for
+    /// plastic connections.
+    pub fn to_neat_python(&self) -> Result<String, NeatPythonError> {
+        let connections = self.get_connections();
+
+        if connections.iter().any(|c| c.get_hebbian().is_some()) {
+            return Err(NeatPythonError::Plastic);
+        }
+
+        let mut nodes = self.get_nodes();
+        nodes.sort_by_key(|node| node.get_position());
+
+        let mut keys = HashMap::new();
+        let (mut inputs, mut outputs, mut hidden) = (0i64, 0i64, 0i64);
+
+        for node in &nodes {
+            let key = match node.get_type() {
+                NeuronType::Input => {
+                    inputs += 1;
+                    -inputs
+                }
+                NeuronType::Output => {
+                    outputs += 1;
+                    outputs - 1
+                }
+                _ => {
+                    hidden += 1;
+                    hidden - 1
+                }
+            };
+            keys.insert(node.get_id(), (node.get_type(), key));
+        }
+
+        let disabled = nodes
+            .iter()
+            .filter(|node| !node.get_enabled())
+            .map(|node| node.get_id())
+            .collect::<HashSet<_>>();
+
+        let mut python = PythonGenome {
+            key: 0,
+            fitness: None,
+            nodes: vec![],
+            connections: vec![],
+        };
+        let mut scales = HashMap::new();
+
+        for node in &nodes {
+            let (neuron_type, key) = &keys[&node.get_id()];
+            let key = match neuron_type {
+                NeuronType::Input => continue,
+                NeuronType::Output => *key,
+                _ => key + outputs,
+            };
+
+            let (activation, scale) = activation_to_name(node.get_activation())?;
+            scales.insert(node.get_id(), (scale, node.get_aggregation()));
+
+            python.nodes.push(PythonNode {
+                key,
+                bias: node.get_bias() / scale,
+                response: 1.0,
+                activation: activation.to_string(),
+                aggregation: aggregation_to_name(node.get_aggregation()).to_string(),
+            });
+        }
+
+        let key = |id: u32| -> Result<i64, NeatPythonError> {
+            match keys.get(&id) {
+                Some((NeuronType::Input | NeuronType::Output, key)) => Ok(*key),
+                Some((_, key)) => Ok(key + outputs),
+                None => Err(NeatPythonError::UnknownNode(id as i64)),
+            }
+        };
+
+        let links = connections
+            .iter()
+            .map(|c| {
+                (
+                    c.get_from(),
+                    c.get_to(),
+                    c.get_enabled()
+                        && !disabled.contains(&c.get_from())
+                        && !disabled.contains(&c.get_to()),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        for (i, (connection, (from, to, enabled))) in connections.iter().zip(&links).enumerate() {
+            let Some((scale, aggregation)) = scales.get(to) else {
+                return Err(NeatPythonError::UnknownNode(*to as i64));
+            };
+
+            let weight = match aggregation {
+                Aggregation::Product if product_carrier(&links, *to) != Some(i) => {
+                    connection.get_weight()
+                }
+                _ => connection.get_weight() / scale,
+            };
+
+            python.connections.push(PythonConnection {
+                key: (key(*from)?, key(*to)?),
+                weight,
+                enabled: *enabled,
+            });
+        }
+
+        Ok(serde_json::to_string(&python)?)
+    }
+}
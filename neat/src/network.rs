@@ -1,17 +1,41 @@
 use std::ops::{Add, Mul};
+use std::sync::Mutex;
 
 use ndarray::{concatenate, s, Array1, Array2, Axis};
 use tracing::debug;
 
+use vivalaakam_neuro_utils::Aggregation;
+
 use crate::neuron::Neuron;
 use crate::neuron_type::NeuronType;
 
 /// Represents a computational neural network built from a genome.
-#[derive(Default, Clone)]
+///
+/// Networks with plastic connections keep their current weights between
+/// calls of `activate`; `reset` restores the weights of the genome. Plasticity only
+/// applies to `activate` and `activate_sequence`: `activate_matrix` always uses the
+/// genome weights.
+#[derive(Default)]
 pub struct Network {
     inputs: usize,
     outputs: usize,
     neurons: Vec<Neuron>,
+    plastic: bool,
+    weights: Mutex<Vec<Vec<f32>>>,
+    weight_bounds: Option<(f32, f32)>,
+}
+
+impl Clone for Network {
+    fn clone(&self) -> Self {
+        Network {
+            inputs: self.inputs,
+            outputs: self.outputs,
+            neurons: self.neurons.clone(),
+            plastic: self.plastic,
+            weights: Mutex::new(self.weights.lock().unwrap().clone()),
+            weight_bounds: self.weight_bounds,
+        }
+    }
 }
 
 impl Network {
@@ -26,30 +50,112 @@ impl Network {
                     _ => (i, o),
                 });
 
+        let plastic = neurons.iter().any(|neuron| {
+            neuron
+                .get_connections()
+                .iter()
+                .any(|link| link.get_hebbian().is_some())
+        });
+
+        let weights = Mutex::new(Network::initial_weights(&neurons));
+
         Network {
             neurons,
             inputs,
             outputs,
+            plastic,
+            weights,
+            weight_bounds: None,
         }
     }
 
+    /// Keeps plastic weights within `min..=max`, e.g. `Config::connection_weight_min`
+    /// and `Config::connection_weight_max`; unbounded by default.
+    pub fn set_weight_bounds(&mut self, min: f32, max: f32) {
+        self.weight_bounds = Some((min, max));
+    }
+
+    /// Returns the bounds set by `set_weight_bounds`.
+    pub fn get_weight_bounds(&self) -> Option<(f32, f32)> {
+        self.weight_bounds
+    }
+
+    fn initial_weights(neurons: &[Neuron]) -> Vec<Vec<f32>> {
+        neurons
+            .iter()
+            .map(|neuron| {
+                neuron
+                    .get_connections()
+                    .iter()
+                    .map(|link| link.get_weight())
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Returns true if the network has at least one plastic connection.
+    pub fn is_plastic(&self) -> bool {
+        self.plastic
+    }
+
+    /// Restores the weights of plastic connections to their genome values.
+    pub fn reset(&self) {
+        *self.weights.lock().unwrap() = Network::initial_weights(&self.neurons);
+    }
+
     /// Activates the network with a single input vector.
+    ///
+    /// Plastic connections update their weights after every activation.
     pub fn activate(&self, inputs: Vec<f32>) -> Vec<f32> {
+        let mut weights = self.plastic.then(|| self.weights.lock().unwrap());
         let mut state = vec![0f32; self.neurons.len()];
-        for neuron in &self.neurons {
+        for (index, neuron) in self.neurons.iter().enumerate() {
             match neuron.get_neuron_type() {
                 NeuronType::Input => {
                     state[neuron.get_position() as usize] = inputs[neuron.get_position() as usize]
                 }
                 _ => {
-                    let value = neuron
-                        .get_connections()
-                        .iter()
-                        .fold(neuron.get_bias(), |a, b| {
-                            a + state[b.get_from() as usize] * b.get_weight()
-                        });
-
-                    state[neuron.get_position() as usize] = neuron.activate(value);
+                    let connections = neuron.get_connections();
+                    let weight = |i: usize| {
+                        weights
+                            .as_ref()
+                            .map_or(connections[i].get_weight(), |w| w[index][i])
+                    };
+
+                    let value = match neuron.get_aggregation() {
+                        Aggregation::Sum => connections
+                            .iter()
+                            .enumerate()
+                            .fold(neuron.get_bias(), |a, (i, b)| {
+                                a + state[b.get_from() as usize] * weight(i)
+                            }),
+                        aggregation => {
+                            let values = connections
+                                .iter()
+                                .enumerate()
+                                .map(|(i, b)| state[b.get_from() as usize] * weight(i))
+                                .collect::<Vec<_>>();
+
+                            neuron.get_bias() + aggregation.aggregate(&values)
+                        }
+                    };
+
+                    let post = neuron.activate(value);
+                    state[neuron.get_position() as usize] = post;
+
+                    if let Some(weights) = weights.as_mut() {
+                        for (i, link) in connections.iter().enumerate() {
+                            if let Some(hebbian) = link.get_hebbian() {
+                                let weight = weights[index][i]
+                                    + hebbian.delta(state[link.get_from() as usize], post);
+
+                                weights[index][i] = match self.weight_bounds {
+                                    Some((min, max)) => weight.clamp(min, max),
+                                    None => weight,
+                                };
+                            }
+                        }
+                    }
                 }
             }
         }
@@ -57,7 +163,22 @@ impl Network {
         state[state.len() - self.outputs..].to_vec()
     }
 
+    /// Activates the network row by row; plastic weights carry over between rows.
+    pub fn activate_sequence(&self, sequence: &Array2<f32>) -> Array2<f32> {
+        let mut outputs = Array2::zeros((sequence.nrows(), self.outputs));
+
+        for (i, row) in sequence.rows().into_iter().enumerate() {
+            outputs
+                .row_mut(i)
+                .assign(&Array1::from(self.activate(row.to_vec())));
+        }
+
+        outputs
+    }
+
     /// Activates the network with a batch (matrix) of inputs.
+    ///
+    /// Rows are independent, so plastic connections use their genome weights.
     pub fn activate_matrix(&self, matrix: &Array2<f32>) -> Array2<f32> {
         let rows_length = matrix.shape()[0];
         let mut state = concatenate(
@@ -75,16 +196,32 @@ impl Network {
             match neuron.get_neuron_type() {
                 NeuronType::Input => {}
                 _ => {
-                    let value = neuron.get_connections().iter().fold(
-                        Array1::from_elem(rows_length, neuron.get_bias()),
-                        |a, b| {
-                            a.add(
-                                &state
-                                    .column(b.get_from() as usize)
-                                    .mul(Array1::from_elem(rows_length, b.get_weight())),
-                            )
-                        },
-                    );
+                    let value = match neuron.get_aggregation() {
+                        Aggregation::Sum => neuron.get_connections().iter().fold(
+                            Array1::from_elem(rows_length, neuron.get_bias()),
+                            |a, b| {
+                                a.add(
+                                    &state
+                                        .column(b.get_from() as usize)
+                                        .mul(Array1::from_elem(rows_length, b.get_weight())),
+                                )
+                            },
+                        ),
+                        aggregation => {
+                            let connections = neuron.get_connections();
+                            let values = Array2::from_shape_fn(
+                                (rows_length, connections.len()),
+                                |(r, c)| {
+                                    state[[r, connections[c].get_from() as usize]]
+                                        * connections[c].get_weight()
+                                },
+                            );
+
+                            values.map_axis(Axis(1), |row| {
+                                neuron.get_bias() + aggregation.aggregate(&row.to_vec())
+                            })
+                        }
+                    };
 
                     state
                         .column_mut(neuron.get_position() as usize)
@@ -1,7 +1,7 @@
 use crate::link::Link;
 use crate::neuron_type::NeuronType;
 use crate::Node;
-use vivalaakam_neuro_utils::Activation;
+use vivalaakam_neuro_utils::{Activation, Aggregation};
 
 #[derive(Default, Clone)]
 pub struct Neuron {
@@ -10,6 +10,7 @@ pub struct Neuron {
     neuron_type: NeuronType,
     connections: Vec<Link>,
     activation: Activation,
+    aggregation: Aggregation,
 }
 
 impl From<Node> for Neuron {
@@ -20,6 +21,7 @@ impl From<Node> for Neuron {
             neuron_type: value.get_type(),
             connections: vec![],
             activation: value.get_activation(),
+            aggregation: value.get_aggregation(),
         }
     }
 }
@@ -37,6 +39,7 @@ impl Neuron {
         bias: f32,
         position: u32,
         activation: Activation,
+        aggregation: Aggregation,
         connections: Vec<Link>,
     ) -> Self {
         Neuron {
@@ -45,6 +48,7 @@ impl Neuron {
             bias,
             connections,
             activation,
+            aggregation,
         }
     }
 
@@ -64,6 +68,10 @@ impl Neuron {
         self.connections.to_vec()
     }
 
+    pub fn get_aggregation(&self) -> Aggregation {
+        self.aggregation
+    }
+
     pub fn activate(&self, value: f32) -> f32 {
         self.activation.activate(value)
     }
@@ -1,6 +1,7 @@
+use crate::binary::BinaryError;
 use crate::neuron_type::NeuronType;
 use serde::{Deserialize, Serialize};
-use vivalaakam_neuro_utils::Activation;
+use vivalaakam_neuro_utils::{Activation, Aggregation};
 
 /// Represents a neuron (node) in the network.
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
@@ -9,6 +10,8 @@ pub struct Node {
     bias: f32,
     enabled: bool,
     activation: Activation,
+    #[serde(default, skip_serializing_if = "Aggregation::is_sum")]
+    aggregation: Aggregation,
     neuron_type: NeuronType,
     position: Option<u32>,
 }
@@ -29,6 +32,7 @@ impl Node {
             position,
             bias,
             activation: activation.unwrap_or_default(),
+            aggregation: Aggregation::default(),
         }
     }
 
@@ -72,6 +76,16 @@ impl Node {
         self.activation
     }
 
+    /// Sets the node's aggregation function.
+    pub fn set_aggregation(&mut self, aggregation: Aggregation) {
+        self.aggregation = aggregation;
+    }
+
+    /// Returns the node's aggregation function.
+    pub fn get_aggregation(&self) -> Aggregation {
+        self.aggregation
+    }
+
     /// Toggles the enabled/disabled state of the node.
     pub fn toggle_enabled(&mut self) {
         self.enabled = !self.enabled
@@ -90,7 +104,7 @@ impl Node {
             enabled,
             self.activation.to_bytes(),
             self.neuron_type.to_bytes(),
-            0,
+            self.aggregation.to_bytes(),
         ]);
 
         vec![
@@ -102,27 +116,34 @@ impl Node {
     }
 
     /// Creates a node from a vector of weights.
+    ///
+    /// Panics when the weights run out; see `try_from_weights`.
     pub fn from_weights(weights: &mut dyn Iterator<Item = f32>) -> Self {
-        let id = weights.next().expect("got not enough weights") as u32;
-        let bias = weights.next().expect("got not enough weights");
-        let position = weights.next().expect("got not enough weights") as u32;
+        Node::try_from_weights(weights).expect("got not enough weights")
+    }
+
+    /// Creates a node from a vector of weights; missing values are `BinaryError::Truncated`.
+    pub fn try_from_weights(weights: &mut dyn Iterator<Item = f32>) -> Result<Self, BinaryError> {
+        let mut next = || weights.next().ok_or(BinaryError::Truncated);
 
-        let info = weights
-            .next()
-            .expect("got not enough weights")
-            .to_le_bytes();
+        let id = next()? as u32;
+        let bias = next()?;
+        let position = next()? as u32;
+        let info = next()?.to_le_bytes();
 
         let enabled = info[0] == 1;
         let activation = Activation::from_bytes(info[1]);
         let neuron_type = NeuronType::from_bytes(info[2]);
+        let aggregation = Aggregation::from_bytes(info[3]);
 
-        Node {
+        Ok(Node {
             id,
             bias,
             enabled,
             activation,
+            aggregation,
             neuron_type,
             position: Some(position),
-        }
+        })
     }
 }
@@ -1,9 +1,9 @@
 use std::cmp::Ordering;
-use std::sync::Mutex;
+use std::sync::{Mutex, OnceLock};
 
 use ndarray::Array2;
 
-use vivalaakam_neuro_shared::{OrganismTrait, OrganismTraitError};
+use vivalaakam_neuro_shared::{FitnessReport, OrganismTrait, OrganismTraitError};
 
 use crate::genome::GenomeError;
 use crate::network::Network;
@@ -12,11 +12,14 @@ use crate::{Config, Genome, NeuronType};
 /// Represents an individual in the population, encapsulating a genome and its network.
 #[derive(Default)]
 pub struct Organism {
-    pub genome: Genome,
+    genome: Genome,
     pub network: Network,
     fitness: Mutex<f32>,
+    report: Mutex<Option<FitnessReport>>,
+    evaluations: Mutex<usize>,
     stagnation: Mutex<usize>,
     genotype: Vec<u32>,
+    hash: OnceLock<String>,
     id: Option<String>,
 }
 
@@ -43,12 +46,43 @@ impl Organism {
             genome,
             network,
             fitness: Mutex::new(0.0),
+            report: Mutex::new(None),
+            evaluations: Mutex::new(0),
             stagnation: Mutex::new(0),
             genotype,
+            hash: OnceLock::new(),
             id: None,
         }
     }
 
+    /// Keeps plastic weights within the weight bounds of the config.
+    ///
+    /// Organisms created by `mutate` get the bounds on their own.
+    pub fn with_weight_bounds(mut self, config: &Config) -> Self {
+        self.network
+            .set_weight_bounds(config.connection_weight_min, config.connection_weight_max);
+        self
+    }
+
+    /// Returns the genome of the organism.
+    pub fn get_genome(&self) -> &Genome {
+        &self.genome
+    }
+
+    /// Replaces the genome and rebuilds the network, keeping its weight bounds and the id.
+    ///
+    /// Fitness, report, stagnation and the cached hash start over as in `new`.
+    pub fn set_genome(&mut self, genome: Genome) {
+        let mut organism = Organism::new(genome);
+
+        if let Some((min, max)) = self.network.get_weight_bounds() {
+            organism.network.set_weight_bounds(min, max);
+        }
+
+        organism.id = self.id.take();
+        *self = organism;
+    }
+
     /// Returns the genotype (hidden node IDs) of the organism.
     pub fn get_genotype(&self) -> Vec<u32> {
         self.genotype.to_vec()
@@ -79,6 +113,24 @@ impl OrganismTrait<Config> for Organism {
         self.network.activate_matrix(matrix)
     }
 
+    fn activate_sequence(&self, sequence: &Array2<f32>) -> Array2<f32> {
+        self.network.activate_sequence(sequence)
+    }
+
+    fn get_genotype(&self) -> Vec<u32> {
+        self.genotype.to_vec()
+    }
+
+    /// Returns the content hash of the genome, see `Genome::get_hash`; it is
+    /// computed on first use.
+    fn get_hash(&self) -> Option<String> {
+        Some(self.hash.get_or_init(|| self.genome.get_hash()).clone())
+    }
+
+    fn reset(&self) {
+        self.network.reset()
+    }
+
     fn set_fitness(&self, fitness: f32) {
         let mut data = self.fitness.lock().unwrap();
         *data = fitness
@@ -88,6 +140,32 @@ impl OrganismTrait<Config> for Organism {
         *self.fitness.lock().unwrap()
     }
 
+    fn set_report(&self, report: FitnessReport) {
+        self.set_fitness(report.score);
+        *self.report.lock().unwrap() = Some(report);
+    }
+
+    fn get_report(&self) -> Option<FitnessReport> {
+        self.report.lock().unwrap().clone()
+    }
+
+    fn add_evaluation(&self, fitness: f32, smoothing: f32) {
+        let mut evaluations = self.evaluations.lock().unwrap();
+        let mut data = self.fitness.lock().unwrap();
+
+        if *evaluations == 0 {
+            *data = fitness;
+        } else {
+            *data += (fitness - *data) * smoothing;
+        }
+
+        *evaluations += 1;
+    }
+
+    fn get_evaluations(&self) -> usize {
+        *self.evaluations.lock().unwrap()
+    }
+
     fn inc_stagnation(&self) {
         let mut data = self.stagnation.lock().unwrap();
         *data += 1;
@@ -97,12 +175,34 @@ impl OrganismTrait<Config> for Organism {
         self.stagnation.lock().unwrap().to_owned()
     }
 
+    /// Switches `Config::phase` from the mean complexity of the organisms.
+    fn adapt_config(config: &mut Config, organisms: &[Self]) {
+        if organisms.is_empty() {
+            return;
+        }
+
+        let complexity = organisms
+            .iter()
+            .map(|organism| organism.genome.get_complexity())
+            .sum::<usize>() as f32
+            / organisms.len() as f32;
+
+        config.phase = config.phase.next(complexity, config);
+    }
+
     fn mutate(&self, child: Option<&Self>, config: &Config) -> Result<Self, OrganismTraitError> {
-        let genome = child.map(|organism| &organism.genome);
+        let genome = child.map(|organism| {
+            let fitness = self
+                .get_fitness()
+                .partial_cmp(&organism.get_fitness())
+                .unwrap_or(Ordering::Equal);
+
+            (&organism.genome, fitness)
+        });
 
         self.genome
             .mutate(genome, config)
-            .map(Organism::new)
+            .map(|genome| Organism::new(genome).with_weight_bounds(config))
             .map_err(OrganismTraitError::from)
     }
 }
@@ -113,8 +213,11 @@ impl Clone for Organism {
             genome: self.genome.clone(),
             network: self.network.clone(),
             fitness: Mutex::new(self.get_fitness()),
+            report: Mutex::new(self.get_report()),
+            evaluations: Mutex::new(self.get_evaluations()),
             stagnation: Mutex::new(self.get_stagnation()),
             genotype: self.genotype.clone(),
+            hash: self.hash.clone(),
             id: self.id.clone(),
         }
     }
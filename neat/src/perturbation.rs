@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+use vivalaakam_neuro_utils::random::{get_random_gaussian, get_random_weight};
+
+/// Distribution used to perturb weights and biases during mutation.
+#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub enum Perturbation {
+    /// Uniform delta in `[-power, power)`.
+    #[default]
+    Uniform,
+    /// Normally distributed delta with standard deviation `power`.
+    Gaussian,
+}
+
+impl Perturbation {
+    /// Samples a delta scaled by `power`.
+    pub fn sample(&self, power: f32) -> f32 {
+        match self {
+            Perturbation::Uniform => get_random_weight(power),
+            Perturbation::Gaussian => get_random_gaussian(power),
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+
+/// Search phase of the blended complexification / simplification schedule.
+///
+/// `Population` switches `Config::phase` after every epoch from the mean complexity
+/// of its organisms (see `Organism::adapt_config`).
+#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub enum Phase {
+    /// Structural mutations add nodes and connections.
+    #[default]
+    Complexifying,
+    /// Structural mutations delete nodes and connections.
+    Simplifying,
+}
+
+impl Phase {
+    /// Returns the phase for the next epoch given the mean population complexity.
+    ///
+    /// Switches to `Simplifying` once the mean complexity exceeds
+    /// `phase_complexity_ceiling` and back to `Complexifying` once it drops
+    /// below `phase_complexity_floor`.
+    pub fn next(&self, mean_complexity: f32, config: &Config) -> Phase {
+        match self {
+            Phase::Complexifying if mean_complexity > config.phase_complexity_ceiling => {
+                Phase::Simplifying
+            }
+            Phase::Simplifying if mean_complexity < config.phase_complexity_floor => {
+                Phase::Complexifying
+            }
+            phase => *phase,
+        }
+    }
+}
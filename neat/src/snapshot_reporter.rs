@@ -0,0 +1,42 @@
+use std::fs;
+use std::path::PathBuf;
+
+use vivalaakam_neuro_shared::Reporter;
+
+use crate::organism::Organism;
+
+/// Writes the genome of every new champion to `<directory>/champion_<epoch>.json`
+/// and the final best genome to `<directory>/final.json`.
+pub struct SnapshotReporter {
+    directory: PathBuf,
+}
+
+impl SnapshotReporter {
+    /// Creates the directory if it does not exist.
+    pub fn new<P>(directory: P) -> anyhow::Result<Self>
+    where
+        P: Into<PathBuf>,
+    {
+        let directory = directory.into();
+        fs::create_dir_all(&directory)?;
+
+        Ok(SnapshotReporter { directory })
+    }
+}
+
+impl Reporter<Organism> for SnapshotReporter {
+    fn new_champion(&mut self, epoch: usize, champion: &Organism) -> anyhow::Result<()> {
+        fs::write(
+            self.directory.join(format!("champion_{epoch}.json")),
+            champion.as_json(),
+        )?;
+        Ok(())
+    }
+
+    fn run_end(&mut self, _epoch: usize, champion: Option<&Organism>) -> anyhow::Result<()> {
+        if let Some(champion) = champion {
+            fs::write(self.directory.join("final.json"), champion.as_json())?;
+        }
+        Ok(())
+    }
+}
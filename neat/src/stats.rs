@@ -0,0 +1,106 @@
+use std::collections::{BTreeMap, HashSet};
+
+use serde::Serialize;
+use tracing::info;
+
+use vivalaakam_neuro_shared::EpochMetrics;
+
+use crate::organism::Organism;
+
+/// Structure of a single genome, see `Genome::stats`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct GenomeStats {
+    pub nodes_enabled: usize,
+    pub nodes_disabled: usize,
+    pub hidden: usize,
+    pub connections_enabled: usize,
+    pub connections_disabled: usize,
+    /// Longest chain of enabled connections from an input.
+    pub depth: usize,
+    /// Number of enabled non-input nodes per activation function.
+    pub activations: BTreeMap<String, usize>,
+    /// Minimum, maximum and mean weight of enabled connections; zero without connections.
+    pub weight_min: f32,
+    pub weight_max: f32,
+    pub weight_mean: f32,
+}
+
+/// Fitness, complexity and diversity of a population at one epoch.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PopulationStats {
+    /// Size, fitness and species (distinct genotypes) as reported to a `Reporter`.
+    #[serde(flatten)]
+    pub metrics: EpochMetrics,
+    pub complexity_min: usize,
+    pub complexity_max: usize,
+    pub complexity_mean: f32,
+    /// Mean `Genome::get_distance` over all pairs of organisms with a known distance.
+    pub distance_mean: f32,
+}
+
+impl PopulationStats {
+    /// Collects the statistics of the organisms at an epoch.
+    pub fn new(epoch: usize, organisms: &[Organism]) -> Self {
+        let species = organisms
+            .iter()
+            .map(|organism| organism.get_genotype())
+            .collect::<HashSet<_>>()
+            .len();
+        let metrics = EpochMetrics::new(epoch, organisms, species);
+
+        if organisms.is_empty() {
+            return PopulationStats {
+                metrics,
+                ..PopulationStats::default()
+            };
+        }
+
+        let complexity = organisms
+            .iter()
+            .map(|organism| organism.get_genome().get_complexity())
+            .collect::<Vec<_>>();
+
+        let mut distance = 0f64;
+        let mut pairs = 0usize;
+
+        for (i, a) in organisms.iter().enumerate() {
+            for b in &organisms[i + 1..] {
+                // `i32::MAX` marks genomes whose distance could not be computed.
+                let value = a.get_genome().get_distance(b.get_genome());
+
+                if value != i32::MAX {
+                    distance += value as f64;
+                    pairs += 1;
+                }
+            }
+        }
+
+        PopulationStats {
+            metrics,
+            complexity_min: complexity.iter().copied().min().unwrap_or_default(),
+            complexity_max: complexity.iter().copied().max().unwrap_or_default(),
+            complexity_mean: complexity.iter().sum::<usize>() as f32 / organisms.len() as f32,
+            distance_mean: if pairs > 0 {
+                (distance / pairs as f64) as f32
+            } else {
+                0.0
+            },
+        }
+    }
+
+    /// Emits the statistics as a `tracing` event.
+    pub fn emit(&self) {
+        info!(
+            epoch = self.metrics.epoch,
+            size = self.metrics.size,
+            fitness_mean = self.metrics.fitness_mean,
+            fitness_max = self.metrics.fitness_max,
+            species = self.metrics.species,
+            complexity_min = self.complexity_min,
+            complexity_max = self.complexity_max,
+            complexity_mean = self.complexity_mean,
+            distance_mean = self.distance_mean,
+            "population stats"
+        );
+    }
+}
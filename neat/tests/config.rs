@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod tests {
+    use vivalaakam_neuro_neat::{Config, Perturbation, Phase};
+    use vivalaakam_neuro_utils::ConfigError;
+
+    #[test]
+    fn from_toml() {
+        let config = Config::from_toml(
+            r#"
+            add_node = 0.3
+            node_max = 20
+            connection_weight_perturbation = "Gaussian"
+            "#,
+        )
+        .expect("can't parse config");
+
+        assert_eq!(config.add_node, 0.3);
+        assert_eq!(config.node_max, 20);
+        assert_eq!(
+            config.connection_weight_perturbation,
+            Perturbation::Gaussian
+        );
+        assert_eq!(config.add_connection, Config::default().add_connection);
+
+        let config = Config::from_json(r#"{"phase": "Simplifying"}"#).expect("can't parse config");
+        assert_eq!(config.phase, Phase::Simplifying);
+    }
+
+    #[test]
+    fn with_env() {
+        std::env::set_var("NEAT_CONNECTION_HEBBIAN_DELTA", "0.25");
+        let config = Config::default().with_env();
+        std::env::remove_var("NEAT_CONNECTION_HEBBIAN_DELTA");
+
+        assert_eq!(
+            config.expect("can't read env").connection_hebbian_delta,
+            0.25
+        );
+
+        std::env::set_var("NEAT_CONNECTION_HEBBIAN_PROB", "high");
+        let config = Config::default().with_env();
+        std::env::remove_var("NEAT_CONNECTION_HEBBIAN_PROB");
+
+        assert!(matches!(config, Err(ConfigError::Env { .. })));
+    }
+
+    #[test]
+    fn validate() {
+        assert!(Config::default().validate(2, 1).is_ok());
+
+        let config = Config {
+            add_node: 1.5,
+            ..Config::default()
+        };
+        assert!(matches!(
+            config.validate(2, 1),
+            Err(ConfigError::Probability { .. })
+        ));
+
+        let config = Config {
+            node_max: 2,
+            ..Config::default()
+        };
+        assert!(matches!(
+            config.validate(2, 1),
+            Err(ConfigError::Invalid(_))
+        ));
+    }
+}
@@ -0,0 +1,162 @@
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+    use std::thread;
+    use std::time::Duration;
+
+    use vivalaakam_neuro_neat::{
+        Config, Coordinator, DistributedError, Genome, Organism, WireFormat, Worker, WorkerAddress,
+    };
+    use vivalaakam_neuro_shared::{OrganismTrait, Population};
+    use vivalaakam_neuro_utils::Activation;
+
+    fn spawn_worker<F>(evaluate: F) -> WorkerAddress
+    where
+        F: Fn(&Genome) -> anyhow::Result<f32> + Send + Sync + 'static,
+    {
+        serve(
+            Worker::bind("127.0.0.1:0").expect("can't bind worker"),
+            evaluate,
+        )
+    }
+
+    fn serve<F>(worker: Worker, evaluate: F) -> WorkerAddress
+    where
+        F: Fn(&Genome) -> anyhow::Result<f32> + Send + Sync + 'static,
+    {
+        let address = worker.local_addr().expect("can't get address");
+
+        thread::spawn(move || worker.run(evaluate));
+
+        address
+    }
+
+    /// Port `0` can't be listened on, so no other test can ever answer there.
+    fn dead_address() -> WorkerAddress {
+        WorkerAddress::Tcp(SocketAddr::from(([127, 0, 0, 1], 0)))
+    }
+
+    fn connections(genome: &Genome) -> anyhow::Result<f32> {
+        Ok(genome.get_connections().len() as f32)
+    }
+
+    fn genomes() -> Vec<Genome> {
+        let config = Config::default();
+
+        (0..12)
+            .map(|i| {
+                Genome::generate_genome(1 + i % 4, 1, vec![], Some(Activation::Sigmoid), &config)
+                    .expect("can't create genome")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn evaluate() {
+        let genomes = genomes();
+        let refs = genomes.iter().collect::<Vec<_>>();
+        let expected = genomes
+            .iter()
+            .map(|genome| genome.get_connections().len() as f32)
+            .collect::<Vec<_>>();
+
+        for format in [WireFormat::Json, WireFormat::Binary] {
+            let coordinator = Coordinator::new(vec![
+                spawn_worker(connections),
+                dead_address(),
+                spawn_worker(connections),
+            ])
+            .with_format(format)
+            .with_timeout(Duration::from_secs(5));
+
+            assert_eq!(
+                coordinator.evaluate(&refs).expect("can't evaluate"),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn timeout_and_failures() {
+        let genomes = genomes();
+        let refs = genomes.iter().collect::<Vec<_>>();
+
+        let slow = spawn_worker(|genome| {
+            thread::sleep(Duration::from_secs(2));
+            connections(genome)
+        });
+
+        let coordinator = Coordinator::new(vec![slow, spawn_worker(connections)])
+            .with_timeout(Duration::from_millis(200));
+        assert_eq!(
+            coordinator.evaluate(&refs).expect("can't evaluate").len(),
+            12
+        );
+
+        let coordinator =
+            Coordinator::new(vec![spawn_worker(|_| anyhow::bail!("simulator crashed"))])
+                .with_retries(1);
+        assert!(matches!(
+            coordinator.evaluate(&refs),
+            Err(DistributedError::Evaluation { .. })
+        ));
+
+        let coordinator = Coordinator::new(vec![dead_address()]);
+        assert!(matches!(
+            coordinator.evaluate(&refs),
+            Err(DistributedError::NoWorkers(12))
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn unix_socket() {
+        let path = std::env::temp_dir().join(format!("neat-worker-{}.sock", std::process::id()));
+        std::fs::remove_file(&path).ok();
+
+        let worker = Worker::bind_unix(&path).expect("can't bind worker");
+        let address = serve(worker, connections);
+        assert_eq!(address, WorkerAddress::Unix(path.clone()));
+
+        let genomes = genomes();
+        let refs = genomes.iter().collect::<Vec<_>>();
+        let coordinator = Coordinator::new(vec![address, spawn_worker(connections)]);
+
+        assert_eq!(
+            coordinator.evaluate(&refs).expect("can't evaluate"),
+            genomes
+                .iter()
+                .map(|genome| genome.get_connections().len() as f32)
+                .collect::<Vec<_>>()
+        );
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn epoch_batch() {
+        let config = Config::default();
+        let mut population: Population<Config, Organism> = Population::new(6, config.clone());
+
+        for genome in genomes().into_iter().take(6) {
+            population.add_organism(Organism::new(genome));
+        }
+
+        let mut coordinator =
+            Coordinator::new(vec![spawn_worker(connections), spawn_worker(connections)]);
+
+        population
+            .calculate_fitness_batch(&mut coordinator)
+            .expect("can't evaluate");
+        let best = population
+            .epoch_batch(&mut coordinator)
+            .expect("can't evaluate")
+            .expect("population is empty");
+
+        assert_eq!(
+            best.get_fitness(),
+            best.get_genome().get_connections().len() as f32
+        );
+        assert_eq!(population.len(), 6);
+    }
+}
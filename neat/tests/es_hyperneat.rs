@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod tests {
+    use vivalaakam_neuro_neat::{
+        Connection, EsSubstrate, EsSubstrateConfig, Genome, NeuronType, Node, SubstrateError,
+    };
+    use vivalaakam_neuro_utils::Activation;
+
+    fn get_cppn() -> Genome {
+        let nodes = vec![
+            Node::new(NeuronType::Input, 0, 0.0, None, Some(0)),
+            Node::new(NeuronType::Input, 1, 0.0, None, Some(1)),
+            Node::new(NeuronType::Input, 2, 0.0, None, Some(2)),
+            Node::new(NeuronType::Input, 3, 0.0, None, Some(3)),
+            Node::new(
+                NeuronType::Output,
+                4,
+                0.0,
+                Some(Activation::Gaussian),
+                Some(4),
+            ),
+        ];
+        let connections = vec![Connection::new(0, 4, 16.0), Connection::new(2, 4, -16.0)];
+
+        Genome::new(nodes, connections).expect("can't create genome")
+    }
+
+    #[test]
+    fn build_network() {
+        let substrate =
+            EsSubstrate::new(vec![(-0.5625, -1.0), (0.5625, -1.0)], vec![(-0.5625, 1.0)]);
+
+        let genome = substrate
+            .build_genome(&get_cppn(), &EsSubstrateConfig::default())
+            .expect("can't build genome");
+
+        let hidden = genome.get_hidden_node_ids();
+        assert_eq!(hidden.len(), 16);
+
+        let connections = genome.get_connections();
+        for id in hidden {
+            assert!(connections.iter().any(|c| c.get_to() == id));
+            assert!(connections.iter().any(|c| c.get_from() == id));
+        }
+
+        assert!(connections.iter().all(|c| c.get_from() != 1));
+
+        let network = substrate
+            .build_network(&get_cppn(), &EsSubstrateConfig::default())
+            .expect("can't build network");
+
+        assert_eq!(network.activate(vec![1.0, 1.0]).len(), 1);
+    }
+
+    #[test]
+    fn cppn_inputs() {
+        let substrate = EsSubstrate::new(vec![(0.0, -1.0)], vec![(0.0, 1.0)]);
+
+        let nodes = vec![
+            Node::new(NeuronType::Input, 0, 0.0, None, Some(0)),
+            Node::new(NeuronType::Output, 1, 0.0, None, Some(1)),
+        ];
+        let cppn = Genome::new(nodes, vec![]).expect("can't create genome");
+
+        assert!(matches!(
+            substrate.build_genome(&cppn, &EsSubstrateConfig::default()),
+            Err(SubstrateError::CppnInputs {
+                expected: 4,
+                got: 1
+            })
+        ));
+    }
+}
@@ -1,10 +1,15 @@
 #[cfg(test)]
 mod tests {
+    use std::cmp::Ordering;
+
     use ndarray::Array2;
     use serde_json::json;
 
-    use vivalaakam_neuro_neat::{Config, Connection, Genome, NeuronType, Node};
-    use vivalaakam_neuro_utils::Activation;
+    use vivalaakam_neuro_neat::{
+        BinaryError, Config, Connection, Genome, Hebbian, MutationRates, NeuronType, Node,
+        Perturbation, Phase,
+    };
+    use vivalaakam_neuro_utils::{Activation, Aggregation};
 
     #[test]
     fn it_works() {
@@ -105,7 +110,7 @@ mod tests {
         let network = genome.get_network();
 
         assert_eq!(network.activate(vec![1.0]), vec![0.9996177]);
-        assert_eq!(network.activate(vec![1.2]), vec![0.9998430]);
+        assert_eq!(network.activate(vec![1.2]), vec![0.999843]);
         assert_eq!(network.activate(vec![0.5]), vec![0.99639386]);
         assert_eq!(network.activate(vec![0.1]), vec![0.977193]);
     }
@@ -150,12 +155,52 @@ mod tests {
 
         let inputs = Array2::from_shape_vec((4, 1), vec![1.0, 1.2, 0.5, 0.1]).expect("input error");
         let outputs =
-            Array2::from_shape_vec((4, 1), vec![0.9996177, 0.9998430, 0.99639386, 0.977193])
+            Array2::from_shape_vec((4, 1), vec![0.9996177, 0.999843, 0.99639386, 0.977193])
                 .expect("output error");
 
         assert_eq!(network.activate_matrix(&inputs), outputs);
     }
 
+    #[test]
+    fn get_network_aggregation() {
+        let mut product = Node::new(NeuronType::Output, 2, 0.5, None, Some(3));
+        product.set_aggregation(Aggregation::Product);
+
+        let mut max = Node::new(NeuronType::Output, 3, 0.0, None, Some(4));
+        max.set_aggregation(Aggregation::Max);
+
+        let nodes = vec![
+            Node::new(NeuronType::Input, 0, 0.0, None, Some(1)),
+            Node::new(NeuronType::Input, 1, 0.0, None, Some(2)),
+            product,
+            max,
+        ];
+        let connections = vec![
+            Connection::new(0, 2, 2.0),
+            Connection::new(1, 2, 1.0),
+            Connection::new(0, 3, 1.0),
+            Connection::new(1, 3, -1.0),
+        ];
+
+        let genome = Genome::new(nodes, connections).expect("can't create genome");
+        let network = genome.get_network();
+
+        assert_eq!(network.activate(vec![3.0, 4.0]), vec![24.5, 3.0]);
+
+        let inputs =
+            Array2::from_shape_vec((2, 2), vec![3.0, 4.0, -1.0, 2.0]).expect("input error");
+        let outputs =
+            Array2::from_shape_vec((2, 2), vec![24.5, 3.0, -3.5, -1.0]).expect("output error");
+
+        assert_eq!(network.activate_matrix(&inputs), outputs);
+
+        let restored = Genome::from_weights(genome.to_weights());
+        assert_eq!(
+            restored.get_network().activate(vec![3.0, 4.0]),
+            vec![24.5, 3.0]
+        );
+    }
+
     #[test]
     fn add_node() {
         let config = Config {
@@ -199,6 +244,64 @@ mod tests {
         );
     }
 
+    #[test]
+    fn mutate_connection_weights() {
+        let config = Config {
+            connection_weight_delta: 10.0,
+            connection_weight_perturbation: Perturbation::Gaussian,
+            connection_weight_min: -1.0,
+            connection_weight_max: 1.0,
+            ..Config::default()
+        };
+
+        let nodes = vec![
+            Node::new(NeuronType::Input, 0, 0f32, None, None),
+            Node::new(NeuronType::Input, 1, 0f32, None, None),
+            Node::new(NeuronType::Output, 2, 0f32, None, None),
+        ];
+        let connections = vec![Connection::new(0, 2, 0.7f32), Connection::new(1, 2, 0.7f32)];
+
+        let genome = Genome::new(nodes, connections).expect("can't create genome");
+        let new_genome = genome.mutate_connection_weights(&config).unwrap();
+
+        for connection in new_genome.get_connections() {
+            assert_ne!(connection.get_weight(), 0.7f32);
+            assert!((-1.0..=1.0).contains(&connection.get_weight()));
+        }
+    }
+
+    #[test]
+    fn weight_bounds() {
+        let config = Config {
+            connection_weight: 10.0,
+            connection_weight_min: -0.5,
+            connection_weight_max: 0.5,
+            node_bias: 10.0,
+            node_bias_min: -0.1,
+            node_bias_max: 0.1,
+            ..Config::default()
+        };
+
+        let genome = Genome::generate_genome(3, 2, vec![4], Some(Activation::Sigmoid), &config)
+            .expect("can't create genome");
+        let genome = genome.mutate_connection_weights(&config).unwrap();
+
+        for connection in genome.get_connections() {
+            assert!((-0.5..=0.5).contains(&connection.get_weight()));
+        }
+
+        for node in genome.get_nodes() {
+            assert!((-0.1..=0.1).contains(&node.get_bias()));
+        }
+
+        let reversed = Config {
+            connection_weight_min: 0.5,
+            connection_weight_max: -0.5,
+            ..config
+        };
+        assert!(reversed.validate(3, 2).is_err());
+    }
+
     #[test]
     fn mutate_connection_enabled() {
         let nodes = vec![
@@ -260,10 +363,190 @@ mod tests {
 
         let child_genome = Genome::new(nodes, connections).expect("can't create genome");
 
-        let new_genome = genome.mutate_crossover(&child_genome).unwrap();
+        let config = Config::default();
+
+        let new_genome = genome
+            .mutate_crossover(&child_genome, Ordering::Greater, &config)
+            .unwrap();
+
+        assert_eq!(new_genome.get_connections().len(), 4);
+        assert_eq!(new_genome.get_nodes().len(), 4);
+        assert_eq!(new_genome.get_hidden_node_ids(), vec![2]);
 
-        assert_eq!(new_genome.get_connections().len(), 7);
-        assert_eq!(new_genome.get_nodes().len(), 5);
+        let new_genome = genome
+            .mutate_crossover(&child_genome, Ordering::Less, &config)
+            .unwrap();
+
+        assert_eq!(new_genome.get_connections().len(), 4);
+        assert_eq!(new_genome.get_nodes().len(), 4);
+        assert_eq!(new_genome.get_hidden_node_ids(), vec![3]);
+    }
+
+    #[test]
+    fn mutate_crossover_disabled() {
+        let nodes = vec![
+            Node::new(NeuronType::Input, 0, 0.0, None, None),
+            Node::new(NeuronType::Output, 1, 0.0, None, None),
+        ];
+
+        let genome = Genome::new(nodes.clone(), vec![Connection::new(0, 1, 0.5)])
+            .expect("can't create genome");
+
+        let mut connection = Connection::new(0, 1, 0.7);
+        connection.set_enabled(false);
+        let child_genome = Genome::new(nodes, vec![connection]).expect("can't create genome");
+
+        let config = Config {
+            crossover_disable: 1.0,
+            ..Config::default()
+        };
+
+        let new_genome = genome
+            .mutate_crossover(&child_genome, Ordering::Equal, &config)
+            .unwrap();
+
+        assert_eq!(new_genome.get_connections().len(), 1);
+        assert!(!new_genome.get_connections()[0].get_enabled());
+    }
+
+    #[test]
+    fn mutate_self_adaptive() {
+        let config = Config {
+            self_adaptive: true,
+            ..Config::default()
+        };
+
+        let genome =
+            Genome::generate_genome(2, 1, vec![], None, &config).expect("can't generate genome");
+        assert!(genome.get_rates().is_none());
+
+        let new_genome = genome.mutate(None, &config).expect("can't mutate genome");
+        let rates = new_genome.get_rates().expect("got no rates");
+
+        assert_ne!(rates, &MutationRates::from(&config));
+        assert!((0.0..=1.0).contains(&rates.add_node));
+
+        let restored: Genome = new_genome.as_json().into();
+        assert_eq!(restored.get_rates(), Some(rates));
+    }
+
+    #[test]
+    fn mutate_self_adaptive_crossover() {
+        let config = Config {
+            self_adaptive: true,
+            self_adaptive_tau: 0.0,
+            crossover: 0.0,
+            ..Config::default()
+        };
+
+        let mut genome =
+            Genome::generate_genome(2, 1, vec![], None, &config).expect("can't generate genome");
+        let mut partner = genome.clone();
+        genome.set_rates(Some(MutationRates {
+            add_node: 0.1,
+            ..MutationRates::from(&config)
+        }));
+        partner.set_rates(Some(MutationRates {
+            add_node: 0.9,
+            ..MutationRates::from(&config)
+        }));
+
+        let child = genome
+            .mutate(Some((&partner, Ordering::Greater)), &config)
+            .expect("can't mutate genome");
+        assert_eq!(child.get_rates().map(|rates| rates.add_node), Some(0.1));
+
+        let config = Config {
+            crossover: 1.0,
+            ..config
+        };
+        let child = genome
+            .mutate(Some((&partner, Ordering::Greater)), &config)
+            .expect("can't mutate genome");
+        assert_eq!(child.get_rates().map(|rates| rates.add_node), Some(0.5));
+    }
+
+    #[test]
+    fn mutate_delete_node() {
+        let nodes = vec![
+            Node::new(NeuronType::Input, 0, 0.0, None, None),
+            Node::new(NeuronType::Hidden, 2, 0.0, None, None),
+            Node::new(NeuronType::Output, 1, 0.0, None, None),
+        ];
+        let connections = vec![
+            Connection::new(0, 1, 0.0),
+            Connection::new(0, 2, 0.0),
+            Connection::new(2, 1, 0.0),
+        ];
+
+        let genome = Genome::new(nodes, connections).expect("can't create genome");
+        assert_eq!(genome.get_complexity(), 4);
+
+        let new_genome = genome.mutate_delete_node().expect("can't delete node");
+
+        assert_eq!(new_genome.get_nodes().len(), 2);
+        assert_eq!(new_genome.get_connections().len(), 1);
+        assert_eq!(new_genome.get_complexity(), 1);
+        assert!(new_genome.mutate_delete_node().is_err());
+
+        let bridge = Genome::new(
+            vec![
+                Node::new(NeuronType::Input, 0, 0.0, None, None),
+                Node::new(NeuronType::Hidden, 2, 0.0, None, None),
+                Node::new(NeuronType::Output, 1, 0.0, None, None),
+            ],
+            vec![Connection::new(0, 2, 0.0), Connection::new(2, 1, 0.0)],
+        )
+        .expect("can't create genome");
+        assert!(bridge.mutate_delete_node().is_err());
+    }
+
+    #[test]
+    fn mutate_delete_connection() {
+        let nodes = vec![
+            Node::new(NeuronType::Input, 0, 0.0, None, None),
+            Node::new(NeuronType::Hidden, 2, 0.0, None, None),
+            Node::new(NeuronType::Output, 1, 0.0, None, None),
+        ];
+        let connections = vec![
+            Connection::new(0, 1, 0.0),
+            Connection::new(0, 2, 0.0),
+            Connection::new(2, 1, 0.0),
+        ];
+
+        let mut genome = Genome::new(nodes, connections).expect("can't create genome");
+        let new_genome = genome
+            .mutate_delete_connection()
+            .expect("can't delete connection");
+        assert_eq!(new_genome.get_connections().len(), 2);
+
+        while let Ok(new_genome) = genome.mutate_delete_connection() {
+            genome = new_genome;
+        }
+
+        let links = genome
+            .get_connections()
+            .iter()
+            .map(|connection| (connection.get_from(), connection.get_to()))
+            .collect::<Vec<_>>();
+        assert!(links == vec![(0, 1)] || links == vec![(0, 2), (2, 1)]);
+    }
+
+    #[test]
+    fn phase_next() {
+        let config = Config {
+            phase_complexity_ceiling: 10.0,
+            phase_complexity_floor: 5.0,
+            ..Config::default()
+        };
+
+        assert_eq!(
+            Phase::Complexifying.next(8.0, &config),
+            Phase::Complexifying
+        );
+        assert_eq!(Phase::Complexifying.next(11.0, &config), Phase::Simplifying);
+        assert_eq!(Phase::Simplifying.next(8.0, &config), Phase::Simplifying);
+        assert_eq!(Phase::Simplifying.next(4.0, &config), Phase::Complexifying);
     }
 
     #[ignore]
@@ -391,8 +674,269 @@ mod tests {
         let network = restored_genome.get_network();
 
         assert_eq!(network.activate(vec![1.0]), vec![0.9996177]);
-        assert_eq!(network.activate(vec![1.2]), vec![0.9998430]);
+        assert_eq!(network.activate(vec![1.2]), vec![0.999843]);
         assert_eq!(network.activate(vec![0.5]), vec![0.99639386]);
         assert_eq!(network.activate(vec![0.1]), vec![0.977193]);
     }
+
+    #[test]
+    fn get_network_plastic() {
+        let nodes = vec![
+            Node::new(NeuronType::Input, 0, 0.0, None, Some(1)),
+            Node::new(
+                NeuronType::Output,
+                1,
+                0.0,
+                Some(Activation::Identity),
+                Some(2),
+            ),
+        ];
+        let mut connection = Connection::new(0, 1, 0.5);
+        connection.set_hebbian(Some(Hebbian {
+            a: 1.0,
+            b: 0.0,
+            c: 0.0,
+            d: 0.0,
+            learning_rate: 0.5,
+        }));
+
+        let genome = Genome::new(nodes, vec![connection]).expect("can't create genome");
+        let network = genome.get_network();
+
+        assert!(network.is_plastic());
+        assert_eq!(network.activate(vec![1.0]), vec![0.5]);
+        assert_eq!(network.activate(vec![1.0]), vec![0.75]);
+
+        network.reset();
+        assert_eq!(network.activate(vec![1.0]), vec![0.5]);
+
+        let restored = Genome::from_weights(genome.to_weights());
+        assert_eq!(
+            restored.get_connections()[0].get_hebbian(),
+            genome.get_connections()[0].get_hebbian()
+        );
+    }
+
+    #[test]
+    fn hebbian_zero_range() {
+        let hebbian = Hebbian::random(0.0);
+
+        assert_eq!(hebbian.delta(1.0, 1.0), 0.0);
+        assert_eq!(hebbian.mutate(0.0), hebbian);
+    }
+
+    #[test]
+    fn stats() {
+        let nodes = vec![
+            Node::new(NeuronType::Input, 0, 0.0, None, Some(1)),
+            Node::new(NeuronType::Input, 1, 0.0, None, Some(2)),
+            Node::new(NeuronType::Hidden, 2, 0.0, Some(Activation::Tanh), Some(3)),
+            Node::new(
+                NeuronType::Output,
+                3,
+                0.0,
+                Some(Activation::Sigmoid),
+                Some(4),
+            ),
+        ];
+        let mut disabled = Connection::new(1, 3, 4.0);
+        disabled.set_enabled(false);
+
+        let connections = vec![
+            Connection::new(0, 2, -1.0),
+            Connection::new(2, 3, 2.0),
+            Connection::new(1, 2, 0.5),
+            disabled,
+        ];
+
+        let genome = Genome::new(nodes, connections).expect("can't create genome");
+        let stats = genome.stats();
+
+        assert_eq!(stats.nodes_enabled, 4);
+        assert_eq!(stats.hidden, 1);
+        assert_eq!(stats.connections_enabled, 3);
+        assert_eq!(stats.connections_disabled, 1);
+        assert_eq!(stats.depth, 2);
+        assert_eq!(stats.activations.get("Tanh"), Some(&1));
+        assert_eq!(stats.activations.get("Sigmoid"), Some(&1));
+        assert_eq!(stats.weight_min, -1.0);
+        assert_eq!(stats.weight_max, 2.0);
+        assert_eq!(stats.weight_mean, 0.5);
+    }
+
+    #[test]
+    fn to_dot() {
+        let nodes = vec![
+            Node::new(NeuronType::Input, 0, 0.0, None, Some(1)),
+            Node::new(
+                NeuronType::Output,
+                1,
+                0.5,
+                Some(Activation::Sigmoid),
+                Some(2),
+            ),
+        ];
+        let genome =
+            Genome::new(nodes, vec![Connection::new(0, 1, 0.25)]).expect("can't create genome");
+
+        let dot = genome.to_dot();
+
+        assert!(dot.starts_with("digraph genome {"));
+        assert!(
+            dot.contains("n1 [label=\"1\\nSigmoid\\nb=0.500\", shape=doublecircle, style=solid];")
+        );
+        assert!(dot.contains("n0 -> n1 [label=\"0.250\", style=solid];"));
+    }
+
+    #[test]
+    fn to_bytes() {
+        let nodes = vec![
+            Node::new(NeuronType::Input, 0, 0.0, None, Some(1)),
+            Node::new(
+                NeuronType::Hidden,
+                u32::MAX - 1,
+                0.25,
+                Some(Activation::Tanh),
+                Some(2),
+            ),
+            Node::new(
+                NeuronType::Output,
+                1,
+                -0.5,
+                Some(Activation::Sigmoid),
+                Some(3),
+            ),
+        ];
+        let mut plastic = Connection::new(u32::MAX - 1, 1, -0.75);
+        plastic.set_hebbian(Some(Hebbian {
+            a: 0.1,
+            b: 0.2,
+            c: 0.3,
+            d: 0.4,
+            learning_rate: 0.05,
+        }));
+        let mut disabled = Connection::new(0, 1, 2.0);
+        disabled.set_enabled(false);
+
+        let mut genome = Genome::new(
+            nodes,
+            vec![Connection::new(0, u32::MAX - 1, 0.5), plastic, disabled],
+        )
+        .expect("can't create genome");
+        genome.set_rates(Some(MutationRates::from(&Config::default())));
+
+        let bytes = genome.to_bytes();
+        assert!(bytes.starts_with(b"NEAT"));
+
+        let restored = Genome::from_bytes(&bytes).expect("can't decode genome");
+        assert_eq!(restored.as_json(), genome.as_json());
+
+        let mut corrupted = bytes.to_vec();
+        corrupted[10] ^= 0xff;
+        assert!(matches!(
+            Genome::from_bytes(&corrupted),
+            Err(BinaryError::Checksum { .. })
+        ));
+        assert!(matches!(
+            Genome::from_bytes(&bytes[..bytes.len() - 6]),
+            Err(BinaryError::Checksum { .. })
+        ));
+
+        let legacy = Genome::new(
+            vec![
+                Node::new(NeuronType::Input, 0, 0.0, None, Some(1)),
+                Node::new(NeuronType::Output, 1, 0.5, None, Some(2)),
+            ],
+            vec![Connection::new(0, 1, 0.5)],
+        )
+        .expect("can't create genome");
+        let weights = legacy
+            .to_weights()
+            .iter()
+            .flat_map(|weight| weight.to_le_bytes())
+            .collect::<Vec<_>>();
+
+        let restored = Genome::from_bytes(&weights).expect("can't decode legacy weights");
+        assert_eq!(restored.as_json(), legacy.as_json());
+        assert!(matches!(
+            Genome::from_bytes(&weights[..weights.len() - 4]),
+            Err(BinaryError::Truncated)
+        ));
+        assert!(matches!(
+            Genome::from_bytes(&[weights.as_slice(), &[0; 4]].concat()),
+            Err(BinaryError::Trailing(4))
+        ));
+
+        assert!(matches!(
+            Genome::try_from_weights(&[1.0, 1.0, 1.0, 1.0, -2.0, 0.0]),
+            Err(BinaryError::Magic)
+        ));
+        assert!(matches!(
+            Genome::try_from_weights(&[1.0, 1.0, 1.0]),
+            Err(BinaryError::Truncated)
+        ));
+        assert!(matches!(
+            Node::try_from_weights(&mut [0.0, 0.5].into_iter()),
+            Err(BinaryError::Truncated)
+        ));
+        assert!(matches!(
+            Connection::try_from_weights(
+                &mut [0.0, 1.0, 0.5, f32::from_le_bytes([1, 1, 0, 0]), 0.1].into_iter()
+            ),
+            Err(BinaryError::Truncated)
+        ));
+        assert!(matches!(
+            Hebbian::try_from_weights(&mut [0.1; 4].into_iter()),
+            Err(BinaryError::Truncated)
+        ));
+        assert!(matches!(
+            Genome::from_bytes(b"garbage!"),
+            Err(BinaryError::Magic)
+        ));
+    }
+
+    #[test]
+    fn get_hash() {
+        let nodes = vec![
+            Node::new(NeuronType::Input, 0, 0.0, None, Some(1)),
+            Node::new(NeuronType::Hidden, 2, 0.1, Some(Activation::Tanh), Some(2)),
+            Node::new(NeuronType::Output, 1, 0.2, None, Some(3)),
+        ];
+        let connections = vec![
+            Connection::new(0, 2, 0.5),
+            Connection::new(2, 1, -0.5),
+            Connection::new(0, 1, 1.0),
+        ];
+
+        let genome = Genome::new(nodes.clone(), connections.clone()).expect("can't create genome");
+        let reordered = Genome::new(
+            nodes.iter().rev().cloned().collect(),
+            connections.iter().rev().cloned().collect(),
+        )
+        .expect("can't create genome");
+
+        assert_eq!(genome.get_hash(), reordered.get_hash());
+        assert_eq!(genome.get_topology_hash(), reordered.get_topology_hash());
+
+        let mut changed = connections.clone();
+        changed[0] = Connection::new(0, 2, 0.75);
+        let reweighted = Genome::new(nodes.clone(), changed).expect("can't create genome");
+
+        assert_ne!(genome.get_hash(), reweighted.get_hash());
+        assert_eq!(genome.get_topology_hash(), reweighted.get_topology_hash());
+
+        let mut changed = connections.clone();
+        changed[0].set_hebbian(Some(Hebbian::random(0.0)));
+        let plastic = Genome::new(nodes.clone(), changed).expect("can't create genome");
+
+        assert_ne!(genome.get_hash(), plastic.get_hash());
+        assert_eq!(genome.get_topology_hash(), plastic.get_topology_hash());
+
+        let duplicates = vec![Connection::new(0, 1, 1.0), Connection::new(0, 1, -1.0)];
+        let first = Genome::new(nodes.clone(), duplicates.clone()).expect("can't create genome");
+        let second = Genome::new(nodes, duplicates.into_iter().rev().collect())
+            .expect("can't create genome");
+
+        assert_eq!(first.get_hash(), second.get_hash());
+    }
 }
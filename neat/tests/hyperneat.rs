@@ -0,0 +1,95 @@
+#[cfg(test)]
+mod tests {
+    use vivalaakam_neuro_neat::{
+        Connection, Genome, NeuronType, Node, Substrate, SubstrateConfig, SubstrateError,
+    };
+    use vivalaakam_neuro_utils::Activation;
+
+    fn get_cppn() -> Genome {
+        let nodes = vec![
+            Node::new(NeuronType::Input, 0, 0.0, None, Some(0)),
+            Node::new(NeuronType::Input, 1, 0.0, None, Some(1)),
+            Node::new(NeuronType::Input, 2, 0.0, None, Some(2)),
+            Node::new(NeuronType::Input, 3, 0.0, None, Some(3)),
+            Node::new(NeuronType::Output, 4, 0.0, None, Some(4)),
+        ];
+        let connections = vec![Connection::new(0, 4, 1.0)];
+
+        Genome::new(nodes, connections).expect("can't create genome")
+    }
+
+    #[test]
+    fn build_network() {
+        let substrate = Substrate::new(
+            vec![vec![-1.0, -1.0], vec![1.0, -1.0]],
+            vec![vec![0.0, 1.0]],
+        );
+        let config = SubstrateConfig {
+            output_activation: Activation::Identity,
+            ..SubstrateConfig::default()
+        };
+
+        let cppn = get_cppn();
+
+        let genome = substrate
+            .build_genome(&cppn, &config)
+            .expect("can't build genome");
+        assert_eq!(genome.get_nodes().len(), 3);
+        assert_eq!(genome.get_connections().len(), 2);
+
+        let network = substrate
+            .build_network(&cppn, &config)
+            .expect("can't build network");
+        assert_eq!(network.activate(vec![1.0, 2.0]), vec![3.0]);
+
+        let network = substrate
+            .build_neural_network(&cppn, &config)
+            .expect("can't build network");
+        assert_eq!(network.activate(vec![1.0, 2.0]), vec![3.0]);
+    }
+
+    #[test]
+    fn build_network_hidden() {
+        let substrate = Substrate::new(
+            vec![vec![-1.0, -1.0], vec![1.0, -1.0]],
+            vec![vec![0.0, 1.0]],
+        )
+        .with_hidden_layer(vec![vec![-0.1, 0.0], vec![0.0, 0.0], vec![0.5, 0.0]]);
+        let config = SubstrateConfig::default();
+
+        let genome = substrate
+            .build_genome(&get_cppn(), &config)
+            .expect("can't build genome");
+
+        assert_eq!(genome.get_nodes().len(), 6);
+        assert_eq!(genome.get_hidden_node_ids().len(), 3);
+        assert_eq!(genome.get_connections().len(), 7);
+    }
+
+    #[test]
+    fn cppn_inputs() {
+        let substrate = Substrate::new(vec![vec![-1.0]], vec![vec![1.0]]);
+
+        assert!(matches!(
+            substrate.build_genome(&get_cppn(), &SubstrateConfig::default()),
+            Err(SubstrateError::CppnInputs {
+                expected: 2,
+                got: 4
+            })
+        ));
+    }
+
+    #[test]
+    fn weight_threshold() {
+        let substrate = Substrate::new(vec![vec![-1.0, 0.0]], vec![vec![1.0, 0.0]]);
+        let config = SubstrateConfig {
+            weight_threshold: 1.0,
+            ..SubstrateConfig::default()
+        };
+
+        assert!(matches!(
+            substrate.build_genome(&get_cppn(), &config),
+            Err(SubstrateError::Threshold(_))
+        ));
+    }
+}
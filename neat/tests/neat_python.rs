@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod tests {
+    use vivalaakam_neuro_neat::{Genome, NeatPythonConfig, NeatPythonError};
+    use vivalaakam_neuro_utils::Activation;
+
+    const GENOME: &str = r#"{
+        "key": 12,
+        "fitness": 3.5,
+        "nodes": [
+            {"key": 0, "bias": 0.5, "response": 2.0, "activation": "sigmoid", "aggregation": "sum"},
+            {"key": 1, "bias": 0.1, "response": 1.0, "activation": "tanh", "aggregation": "sum"}
+        ],
+        "connections": [
+            {"key": [-1, 0], "weight": 1.5, "enabled": true},
+            {"key": [-2, 0], "weight": -0.7, "enabled": true},
+            {"key": [-1, 1], "weight": 0.8, "enabled": true},
+            {"key": [1, 0], "weight": 1.2, "enabled": true},
+            {"key": [-2, 1], "weight": 4.0, "enabled": false}
+        ]
+    }"#;
+
+    fn python(x1: f32, x2: f32) -> f32 {
+        let hidden = (2.5 * (0.1 + 0.8 * x1)).tanh();
+        let z = 0.5 + 2.0 * (1.5 * x1 - 0.7 * x2 + 1.2 * hidden);
+        1.0 / (1.0 + (-5.0 * z).exp())
+    }
+
+    #[test]
+    fn genome_round_trip() {
+        let genome = Genome::from_neat_python(GENOME, 2, 1).expect("can't import genome");
+
+        assert_eq!(genome.get_inputs(), 2);
+        assert_eq!(genome.get_outputs(), 1);
+
+        let exported = genome.to_neat_python().expect("can't export genome");
+        let reimported = Genome::from_neat_python(&exported, 2, 1).expect("can't import genome");
+
+        for (x1, x2) in [(0.0, 0.0), (1.0, 0.0), (0.3, -0.8), (-1.0, 1.0)] {
+            let expected = python(x1, x2);
+            assert!((genome.get_network().activate(vec![x1, x2])[0] - expected).abs() < 1e-5);
+            assert!((reimported.get_network().activate(vec![x1, x2])[0] - expected).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn genome_errors() {
+        let unknown = GENOME.replace("[1, 0]", "[7, 0]");
+        assert!(matches!(
+            Genome::from_neat_python(&unknown, 2, 1),
+            Err(NeatPythonError::UnknownNode(7))
+        ));
+
+        let unsupported = GENOME.replace("\"tanh\"", "\"clamped\"");
+        assert!(matches!(
+            Genome::from_neat_python(&unsupported, 2, 1),
+            Err(NeatPythonError::UnsupportedActivation(_))
+        ));
+    }
+
+    #[test]
+    fn config_round_trip() {
+        let ini = r#"
+[NEAT]
+fitness_criterion     = max
+fitness_threshold     = 3.9
+pop_size              = 150
+reset_on_extinction   = False
+
+[DefaultGenome]
+# node activation options
+activation_default      = tanh
+activation_mutate_rate  = 0.0
+activation_options      = tanh
+node_add_prob           = 0.2
+conn_add_prob           = 0.5
+weight_mutate_power     = 0.5
+bias_max_value          = 30.0
+num_hidden              = 0
+num_inputs              = 2
+num_outputs             = 1
+"#;
+
+        let config = NeatPythonConfig::from_ini(ini).expect("can't parse config");
+
+        assert_eq!(config.inputs, 2);
+        assert_eq!(config.outputs, 1);
+        assert_eq!(config.population, 150);
+        assert_eq!(config.fitness_threshold, 3.9);
+        assert!(matches!(config.activation, Activation::Tanh));
+        assert_eq!(config.config.add_node, 0.2);
+        assert_eq!(config.config.add_connection, 0.5);
+        assert_eq!(config.config.node_bias_max, 30.0);
+
+        let exported = NeatPythonConfig::from_ini(&config.to_ini().expect("can't export config"))
+            .expect("can't parse exported config");
+
+        assert_eq!(exported.config.connection_weight_delta, 0.5);
+        assert_eq!(exported.inputs, 2);
+
+        assert!(matches!(
+            NeatPythonConfig::from_ini("[NEAT]\npop_size = 10\n"),
+            Err(NeatPythonError::MissingKey { .. })
+        ));
+    }
+}
@@ -0,0 +1,726 @@
+#[cfg(test)]
+mod tests {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::task::{Context, Poll};
+
+    use ndarray::Array2;
+    use vivalaakam_neuro_neat::{
+        Config, Connection, Genome, Hebbian, NeuronType, Node, Organism, Phase, PopulationStats,
+        SnapshotReporter,
+    };
+
+    use vivalaakam_neuro_shared::{
+        AsyncFitnessTrait, CsvReporter, Dataset, EvaluatorTrait, FitnessCache, FitnessReport,
+        FitnessTrait, Islands, JsonlReporter, KFold, MiniBatch, OrganismTrait, Population,
+        Stateless, Topology, Validation,
+    };
+    use vivalaakam_neuro_utils::Activation;
+
+    #[test]
+    fn add_evaluation() {
+        let config = Config::default();
+        let genome = Genome::generate_genome(2, 1, vec![], Some(Activation::Sigmoid), &config)
+            .expect("can't create genome");
+        let organism = Organism::new(genome);
+
+        organism.add_evaluation(1.0, 0.5);
+        organism.add_evaluation(2.0, 0.5);
+        organism.add_evaluation(6.0, 0.5);
+
+        assert_eq!(organism.get_evaluations(), 3);
+        assert_eq!(organism.get_fitness(), 3.75);
+        assert_eq!(organism.clone().get_evaluations(), 3);
+    }
+
+    struct Counter {
+        calls: usize,
+    }
+
+    impl EvaluatorTrait<Organism, Config> for Counter {
+        fn evaluate(&mut self, _organism: &Organism) -> anyhow::Result<FitnessReport> {
+            self.calls += 1;
+
+            Ok(FitnessReport::new(self.calls as f32).with_diagnostic("calls", self.calls as f32))
+        }
+
+        fn get_fingerprint(&self) -> Option<u64> {
+            Some(0)
+        }
+    }
+
+    /// Fails on the second evaluation.
+    struct Failing {
+        calls: usize,
+    }
+
+    impl EvaluatorTrait<Organism, Config> for Failing {
+        fn evaluate(&mut self, _organism: &Organism) -> anyhow::Result<FitnessReport> {
+            self.calls += 1;
+
+            anyhow::ensure!(self.calls < 2, "evaluation failed");
+            Ok(FitnessReport::new(100.0))
+        }
+    }
+
+    #[test]
+    fn epoch_evaluate() {
+        let config = Config::default();
+        let genome = Genome::generate_genome(2, 1, vec![], Some(Activation::Sigmoid), &config)
+            .expect("can't create genome");
+
+        let mut population: Population<Config, Organism> = Population::new(4, config);
+        for _ in 0..4 {
+            population.add_organism(Organism::new(genome.clone()));
+        }
+
+        let mut counter = Counter { calls: 0 };
+        population
+            .calculate_fitness_evaluate(&mut counter)
+            .expect("can't evaluate");
+        assert_eq!(counter.calls, 4);
+
+        let best = population
+            .epoch_evaluate(&mut counter)
+            .expect("can't evaluate")
+            .expect("population is empty");
+        assert!(best.get_fitness() >= 4.0);
+        let report = best.get_report().expect("got no report");
+        assert_eq!(report.diagnostics.get("calls"), Some(&best.get_fitness()));
+
+        let mut failing = Failing { calls: 0 };
+        let fitness = population
+            .get_organisms()
+            .iter()
+            .map(|organism| organism.get_fitness())
+            .collect::<Vec<_>>();
+        assert!(population.epoch_evaluate(&mut failing).is_err());
+        assert_eq!(population.get_epoch(), 1);
+        assert_eq!(
+            population
+                .get_organisms()
+                .iter()
+                .map(|organism| organism.get_fitness())
+                .collect::<Vec<_>>(),
+            fitness
+        );
+
+        let best = &population.get_organisms()[0];
+        let inputs = Array2::from_shape_vec((1, 2), vec![0.0, 1.0]).expect("");
+        let targets = Array2::from_shape_vec((1, 1), vec![1.0]).expect("");
+        let mut stateless = Stateless::new(Dataset::new(inputs, targets).expect(""));
+        let report = EvaluatorTrait::<Organism, Config>::evaluate(&mut stateless, best)
+            .expect("can't evaluate");
+        assert!(report.score > 0.0 && report.score <= 1.0);
+    }
+
+    #[test]
+    fn reporters() {
+        let directory = std::env::temp_dir().join(format!("neat_reporters_{}", std::process::id()));
+        std::fs::create_dir_all(&directory).expect("can't create directory");
+
+        let config = Config::default();
+        let genome = Genome::generate_genome(2, 1, vec![], Some(Activation::Sigmoid), &config)
+            .expect("can't create genome");
+
+        let mut population: Population<Config, Organism> = Population::new(4, config);
+        for _ in 0..4 {
+            population.add_organism(Organism::new(genome.clone()));
+        }
+
+        population.add_reporter(Box::new(
+            CsvReporter::new(directory.join("run.csv")).expect("can't create reporter"),
+        ));
+        population.add_reporter(Box::new(
+            JsonlReporter::new(directory.join("run.jsonl")).expect("can't create reporter"),
+        ));
+        population.add_reporter(Box::new(
+            SnapshotReporter::new(directory.join("snapshots")).expect("can't create reporter"),
+        ));
+
+        let mut counter = Counter { calls: 0 };
+        for _ in 0..3 {
+            population
+                .epoch_evaluate(&mut counter)
+                .expect("can't evaluate");
+        }
+        population.finish();
+
+        assert_eq!(population.get_epoch(), 3);
+
+        let csv = std::fs::read_to_string(directory.join("run.csv")).expect("no csv");
+        assert_eq!(csv.lines().count(), 4);
+        assert!(csv.starts_with("epoch,size,fitness_max"));
+
+        let jsonl = std::fs::read_to_string(directory.join("run.jsonl")).expect("no jsonl");
+        assert_eq!(jsonl.lines().count(), 3);
+
+        assert!(directory.join("snapshots/champion_0.json").exists());
+        assert!(directory.join("snapshots/final.json").exists());
+
+        std::fs::remove_dir_all(&directory).expect("can't remove directory");
+    }
+
+    /// Config under which offspring are identical to their parents.
+    fn frozen() -> Config {
+        Config {
+            crossover: 0.0,
+            add_node: 0.0,
+            add_connection: 0.0,
+            connection_enabled: 0.0,
+            connection_weight_prob: 0.0,
+            node_enabled: 0.0,
+            node_bias_prob: 0.0,
+            node_activation_prob: 0.0,
+            node_aggregation_prob: 0.0,
+            connection_hebbian_prob: 0.0,
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn epoch_validated() {
+        let inputs = Array2::from_shape_vec(
+            (8, 2),
+            vec![
+                0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 1.0, 1.0, 0.1, 0.1, 0.1, 0.9, 0.9, 0.1, 0.9, 0.9,
+            ],
+        )
+        .expect("");
+        let targets =
+            Array2::from_shape_vec((8, 1), vec![0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0]).expect("");
+        let k_fold = KFold::new(&Dataset::new(inputs, targets).expect(""), 4);
+
+        let config = Config::default();
+        let mut population: Population<Config, Organism> = Population::new(10, config.clone());
+        for _ in 0..10 {
+            let genome = Genome::generate_genome(2, 1, vec![], Some(Activation::Sigmoid), &config)
+                .expect("can't create genome");
+            population.add_organism(Organism::new(genome));
+        }
+        population.calculate_fitness(k_fold.get_train());
+
+        let mut validation = Validation::new();
+        for _ in 0..5 {
+            population.epoch_validated(k_fold.get_train(), &k_fold, &mut validation);
+        }
+
+        let history = validation.get_history();
+        assert_eq!(history.len(), 5);
+        assert_eq!(
+            history.iter().map(|point| point.epoch).collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4]
+        );
+
+        let best = history
+            .iter()
+            .map(|point| point.validation)
+            .fold(f32::MIN, f32::max);
+        let champion = validation.get_champion().expect("got no champion");
+        assert_eq!(validation.get_champion_fitness(), Some(best));
+        assert_eq!(k_fold.calculate(champion), best);
+
+        let folds = k_fold.calculate_folds(champion);
+        assert_eq!(folds.len(), 4);
+        assert_eq!(best, folds.iter().sum::<f32>() / 4.0);
+        assert_ne!(
+            k_fold.get_fingerprint(),
+            k_fold.get_validation().get_fingerprint()
+        );
+        assert_ne!(k_fold.get_validation().len(), 8);
+    }
+
+    #[test]
+    fn reevaluate_elites() {
+        let inputs =
+            Array2::from_shape_vec((4, 2), vec![0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 1.0, 1.0]).expect("");
+        let targets = Array2::from_shape_vec((4, 1), vec![0.0, 1.0, 1.0, 0.0]).expect("");
+        let mut batch = MiniBatch::new(Dataset::new(inputs, targets).expect(""), 2);
+
+        let config = Config::default();
+        let mut population: Population<Config, Organism> = Population::new(6, config.clone());
+        for _ in 0..6 {
+            let genome = Genome::generate_genome(2, 1, vec![], Some(Activation::Sigmoid), &config)
+                .expect("can't create genome");
+            population.add_organism(Organism::new(genome));
+        }
+        population.set_fitness_smoothing(0.5);
+
+        for _ in 0..3 {
+            population.epoch_stochastic(&batch);
+            batch.next_batch();
+        }
+
+        population.reevaluate_elites(batch.get_dataset(), 6);
+        let organism = population.get_organisms()[0].clone();
+        let full = batch.get_dataset().calculate(&organism);
+        assert_eq!(organism.get_fitness(), full);
+
+        let sample = batch.get_batch().calculate(&organism);
+        organism.add_evaluation(sample, 0.5);
+        assert_eq!(organism.get_fitness(), full + (sample - full) * 0.5);
+    }
+
+    #[test]
+    fn population_stats() {
+        let config = Config::default();
+        let genome = Genome::generate_genome(2, 1, vec![], Some(Activation::Sigmoid), &config)
+            .expect("can't create genome");
+
+        let organisms = vec![Organism::new(genome.clone()), Organism::new(genome)];
+        organisms[0].set_fitness(1.0);
+        organisms[1].set_fitness(3.0);
+
+        let stats = PopulationStats::new(7, &organisms);
+        assert_eq!(stats.metrics.epoch, 7);
+        assert_eq!(stats.metrics.size, 2);
+        assert_eq!(stats.metrics.fitness_mean, 2.0);
+        assert_eq!(stats.metrics.species, 1);
+        assert_eq!(stats.complexity_mean, 2.0);
+        assert_eq!(stats.distance_mean, 0.0);
+    }
+
+    #[test]
+    fn phase_advance() {
+        let config = Config {
+            phase_complexity_ceiling: 1.0,
+            phase_complexity_floor: 0.5,
+            ..frozen()
+        };
+        let genome = Genome::generate_genome(2, 1, vec![], Some(Activation::Sigmoid), &config)
+            .expect("can't create genome");
+        let dataset = Dataset::new(
+            Array2::from_shape_vec((1, 2), vec![0.0, 1.0]).expect(""),
+            Array2::from_shape_vec((1, 1), vec![1.0]).expect(""),
+        )
+        .expect("");
+
+        let mut population: Population<Config, Organism> = Population::new(2, config.clone());
+        population.add_organism(Organism::new(genome.clone()));
+        population.add_organism(Organism::new(genome));
+
+        population.epoch(&dataset);
+        assert_eq!(population.get_config().phase, Phase::Simplifying);
+
+        population.set_config(Config {
+            phase: Phase::Simplifying,
+            phase_complexity_ceiling: 20.0,
+            phase_complexity_floor: 10.0,
+            ..config
+        });
+
+        population.epoch(&dataset);
+        assert_eq!(population.get_config().phase, Phase::Complexifying);
+    }
+
+    /// Output of a single activation, so plastic weights change the fitness.
+    struct Once;
+
+    impl FitnessTrait for Once {
+        fn calculate<T, C>(&self, organism: &T) -> f32
+        where
+            T: OrganismTrait<C>,
+        {
+            organism.activate(vec![1.0])[0]
+        }
+    }
+
+    #[test]
+    fn set_genome() {
+        let config = Config::default();
+        let genome = Genome::generate_genome(2, 1, vec![], Some(Activation::Sigmoid), &config)
+            .expect("can't create genome");
+        let other = Genome::generate_genome(2, 1, vec![], Some(Activation::Tanh), &config)
+            .expect("can't create genome");
+
+        let mut organism = Organism::new(genome).with_weight_bounds(&config);
+        organism.set_id("first".to_string());
+        let hash = organism.get_hash();
+
+        organism.set_genome(other.clone());
+        assert_ne!(organism.get_hash(), hash);
+        assert_eq!(organism.get_hash(), Some(other.get_hash()));
+        assert_eq!(organism.get_id().map(String::as_str), Some("first"));
+        assert_eq!(
+            organism.network.get_weight_bounds(),
+            Some((config.connection_weight_min, config.connection_weight_max))
+        );
+    }
+
+    #[test]
+    fn plastic_reset() {
+        let nodes = vec![
+            Node::new(NeuronType::Input, 0, 0.0, None, Some(1)),
+            Node::new(
+                NeuronType::Output,
+                1,
+                0.0,
+                Some(Activation::Identity),
+                Some(2),
+            ),
+        ];
+        let mut connection = Connection::new(0, 1, 0.5);
+        connection.set_hebbian(Some(Hebbian {
+            a: 1.0,
+            b: 0.0,
+            c: 0.0,
+            d: 0.0,
+            learning_rate: 0.5,
+        }));
+        let genome = Genome::new(nodes, vec![connection]).expect("can't create genome");
+
+        let mut population: Population<Config, Organism> = Population::new(1, frozen());
+        population.add_organism(Organism::new(genome.clone()));
+
+        population.calculate_fitness(&Once);
+        population.calculate_fitness(&Once);
+        population.reevaluate_elites(&Once, 1);
+        assert_eq!(population.get_organisms()[0].get_fitness(), 0.5);
+
+        let config = Config {
+            connection_weight_min: -1.0,
+            connection_weight_max: 1.0,
+            ..frozen()
+        };
+        let organism = Organism::new(genome).with_weight_bounds(&config);
+
+        for _ in 0..10 {
+            organism.activate(vec![1.0]);
+        }
+        assert_eq!(organism.activate(vec![1.0]), vec![1.0]);
+    }
+
+    #[test]
+    fn dedup() {
+        let config = frozen();
+        let genome = Genome::generate_genome(2, 1, vec![], Some(Activation::Sigmoid), &config)
+            .expect("can't create genome");
+
+        let mut population: Population<Config, Organism> = Population::new(4, config);
+        for _ in 0..4 {
+            population.add_organism(Organism::new(genome.clone()));
+        }
+        population.set_dedup(true);
+
+        let mut counter = Counter { calls: 0 };
+        population
+            .epoch_evaluate(&mut counter)
+            .expect("can't evaluate");
+
+        assert_eq!(counter.calls, 0);
+        assert_eq!(
+            population.get_organisms()[0].get_hash(),
+            Organism::new(genome).get_hash()
+        );
+    }
+
+    #[test]
+    fn fitness_cache() {
+        let config = frozen();
+        let genome = Genome::generate_genome(2, 1, vec![], Some(Activation::Sigmoid), &config)
+            .expect("can't create genome");
+
+        let mut population: Population<Config, Organism> = Population::new(4, config);
+        for _ in 0..4 {
+            population.add_organism(Organism::new(genome.clone()));
+        }
+        population.set_fitness_cache(16);
+
+        let mut counter = Counter { calls: 0 };
+        population
+            .calculate_fitness_evaluate(&mut counter)
+            .expect("can't evaluate");
+        population
+            .epoch_evaluate(&mut counter)
+            .expect("can't evaluate");
+
+        assert_eq!(counter.calls, 1);
+        let cache = population.get_fitness_cache().expect("cache is disabled");
+        assert_eq!((cache.get_hits(), cache.get_misses()), (7, 1));
+
+        for organism in population.get_organisms() {
+            let report = organism.get_report().expect("report is not cached");
+            assert_eq!(report.diagnostics.get("calls"), Some(&1.0));
+        }
+
+        population.invalidate_fitness_cache();
+        population
+            .calculate_fitness_evaluate(&mut counter)
+            .expect("can't evaluate");
+        assert_eq!(counter.calls, 2);
+
+        let inputs = Array2::from_shape_vec((1, 2), vec![0.0, 1.0]).expect("");
+        let targets = Array2::from_shape_vec((1, 1), vec![1.0]).expect("");
+        let first = Dataset::new(inputs.clone(), targets).expect("");
+        let second =
+            Dataset::new(inputs, Array2::from_shape_vec((1, 1), vec![0.0]).expect("")).expect("");
+
+        population.calculate_fitness(&first);
+        let fitness = population.get_organisms()[0].get_fitness();
+        population.calculate_fitness(&second);
+
+        assert_ne!(population.get_organisms()[0].get_fitness(), fitness);
+    }
+
+    #[test]
+    fn fitness_cache_report() {
+        let config = Config::default();
+        let genome = Genome::generate_genome(2, 1, vec![], Some(Activation::Sigmoid), &config)
+            .expect("can't create genome");
+        let report = FitnessReport::new(2.0)
+            .with_objectives(vec![1.0, 2.0])
+            .with_behavior(vec![0.5])
+            .with_diagnostic("steps", 10.0);
+
+        let mut cache = FitnessCache::new(4);
+        let organism = Organism::new(genome.clone());
+        organism.set_report(report.clone());
+        cache.store(&organism);
+
+        let organism = Organism::new(genome);
+        assert!(cache.lookup(&organism));
+        assert_eq!(organism.get_fitness(), 2.0);
+        assert_eq!(organism.get_report(), Some(report));
+    }
+
+    /// Fitness without a fingerprint.
+    struct Constant;
+
+    impl FitnessTrait for Constant {
+        fn calculate<T, C>(&self, _organism: &T) -> f32
+        where
+            T: OrganismTrait<C>,
+        {
+            1.0
+        }
+    }
+
+    #[test]
+    fn fitness_cache_fingerprint() {
+        let config = frozen();
+        let genome = Genome::generate_genome(2, 1, vec![], Some(Activation::Sigmoid), &config)
+            .expect("can't create genome");
+
+        let mut population: Population<Config, Organism> = Population::new(4, config);
+        for _ in 0..4 {
+            population.add_organism(Organism::new(genome.clone()));
+        }
+        population.set_fitness_cache(16);
+
+        let inputs = Array2::from_shape_vec((2, 2), vec![0.0, 1.0, 1.0, 1.0]).expect("");
+        let first = Dataset::new(
+            inputs.clone(),
+            Array2::from_shape_vec((2, 1), vec![1.0, 0.0]).expect(""),
+        )
+        .expect("");
+        let second = Dataset::new(
+            inputs,
+            Array2::from_shape_vec((2, 1), vec![0.0, 1.0]).expect(""),
+        )
+        .expect("");
+
+        population.epoch(&first);
+        population.calculate_fitness(&second);
+
+        for organism in population.get_organisms() {
+            assert_eq!(organism.get_fitness(), second.calculate(organism));
+        }
+
+        let cache = population.get_fitness_cache().expect("cache is disabled");
+        let lookups = (cache.get_hits(), cache.get_misses());
+
+        let mut mini_batch = MiniBatch::new(first, 1);
+        assert_eq!(mini_batch.get_fingerprint(), None);
+
+        population.calculate_fitness(&mini_batch);
+        mini_batch.next_batch();
+        population.calculate_fitness(&mini_batch);
+
+        for organism in population.get_organisms() {
+            assert_eq!(organism.get_fitness(), mini_batch.calculate(organism));
+        }
+
+        population
+            .calculate_fitness_evaluate(&mut Stateless::new(Constant))
+            .expect("can't evaluate");
+        population.calculate_fitness(&Constant);
+
+        let cache = population.get_fitness_cache().expect("cache is disabled");
+        assert_eq!((cache.get_hits(), cache.get_misses()), lookups);
+        assert!(population
+            .get_organisms()
+            .iter()
+            .all(|organism| organism.get_fitness() == 1.0));
+    }
+
+    #[test]
+    fn islands() {
+        let inputs =
+            Array2::from_shape_vec((4, 2), vec![0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 1.0, 1.0]).expect("");
+        let targets = Array2::from_shape_vec((4, 1), vec![0.0, 1.0, 1.0, 0.0]).expect("");
+        let dataset = Dataset::new(inputs, targets).expect("");
+
+        let configs = [
+            Config {
+                add_node: 0.5,
+                ..Config::default()
+            },
+            Config {
+                add_node: 0.01,
+                ..Config::default()
+            },
+            Config::default(),
+        ];
+
+        let populations = configs
+            .into_iter()
+            .map(|config| {
+                let mut population = Population::new(6, config.clone());
+                for _ in 0..6 {
+                    let genome =
+                        Genome::generate_genome(2, 1, vec![], Some(Activation::Sigmoid), &config)
+                            .expect("can't create genome");
+                    population.add_organism(Organism::new(genome));
+                }
+                population
+            })
+            .collect::<Vec<_>>();
+
+        let mut islands = Islands::new(populations, Topology::Ring)
+            .with_interval(2)
+            .with_migrants(2);
+        islands.calculate_fitness(&dataset);
+
+        for _ in 0..4 {
+            islands.epoch(&dataset);
+        }
+
+        assert_eq!(islands.get_epoch(), 4);
+
+        let best = islands
+            .get_islands()
+            .iter()
+            .map(|island| island.get_organisms()[0].get_fitness())
+            .collect::<Vec<_>>();
+
+        islands.migrate();
+
+        for (from, fitness) in best.iter().enumerate() {
+            let to = (from + 1) % best.len();
+            let island = &islands.get_islands()[to];
+
+            assert_eq!(island.len(), 6);
+            assert!(island.get_organisms()[0].get_fitness() >= *fitness);
+        }
+
+        assert_eq!(
+            islands.get_best().map(|best| best.get_fitness()),
+            best.iter().copied().reduce(f32::max)
+        );
+        assert_eq!(Topology::FullyConnected.targets(1, 3), vec![0, 2]);
+    }
+
+    #[test]
+    fn immigrate() {
+        let config = Config::default();
+        let genome = Genome::generate_genome(2, 1, vec![], Some(Activation::Sigmoid), &config)
+            .expect("can't create genome");
+
+        let mut population: Population<Config, Organism> = Population::new(3, config);
+        for fitness in [1.0, 3.0, 2.0] {
+            let organism = Organism::new(genome.clone());
+            organism.set_fitness(fitness);
+            population.add_organism(organism);
+        }
+
+        let migrants = [0.5, 4.0].map(|fitness| {
+            let organism = Organism::new(genome.clone());
+            organism.set_fitness(fitness);
+            organism
+        });
+        population.immigrate(migrants.into());
+
+        assert_eq!(
+            population
+                .get_organisms()
+                .iter()
+                .map(|organism| organism.get_fitness())
+                .collect::<Vec<_>>(),
+            vec![4.0, 3.0, 0.5]
+        );
+    }
+
+    /// Pending once before completing, so every evaluation overlaps with the others.
+    struct YieldNow(bool);
+
+    impl Future for YieldNow {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if self.0 {
+                return Poll::Ready(());
+            }
+
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+
+    struct Remote {
+        dataset: Dataset,
+        in_flight: AtomicUsize,
+        peak: AtomicUsize,
+    }
+
+    impl AsyncFitnessTrait for Remote {
+        async fn calculate<T, C>(&self, organism: &T) -> f32
+        where
+            T: OrganismTrait<C> + Sync,
+        {
+            let current = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(current, Ordering::SeqCst);
+
+            YieldNow(false).await;
+
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            self.dataset.calculate(organism)
+        }
+    }
+
+    #[test]
+    fn epoch_async() {
+        let inputs =
+            Array2::from_shape_vec((4, 2), vec![0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 1.0, 1.0]).expect("");
+        let targets = Array2::from_shape_vec((4, 1), vec![0.0, 1.0, 1.0, 0.0]).expect("");
+        let remote = Remote {
+            dataset: Dataset::new(inputs, targets).expect(""),
+            in_flight: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+        };
+
+        let config = Config::default();
+        let mut population: Population<Config, Organism> = Population::new(10, config.clone());
+        for _ in 0..10 {
+            let genome = Genome::generate_genome(2, 1, vec![], Some(Activation::Sigmoid), &config)
+                .expect("can't create genome");
+            population.add_organism(Organism::new(genome));
+        }
+
+        fn assert_send<F: Future + Send>(future: F) -> F {
+            future
+        }
+
+        futures::executor::block_on(assert_send(population.calculate_fitness_async(&remote, 3)));
+        for _ in 0..3 {
+            futures::executor::block_on(assert_send(population.epoch_async(&remote, 3)));
+        }
+
+        assert_eq!(remote.peak.load(Ordering::SeqCst), 3);
+        assert_eq!(population.len(), 10);
+
+        let best = population.get_organisms()[0].clone();
+        assert_eq!(best.get_fitness(), remote.dataset.calculate(&best));
+    }
+}
@@ -0,0 +1,84 @@
+#[cfg(test)]
+mod tests {
+    use ndarray::Array2;
+
+    use vivalaakam_neuro_neat::{Connection, Genome, Hebbian, NeuronType, Node, Organism};
+    use vivalaakam_neuro_shared::{FitnessTrait, Metric, OrganismTrait, Sequence};
+    use vivalaakam_neuro_utils::Activation;
+
+    fn organism() -> Organism {
+        let nodes = vec![
+            Node::new(NeuronType::Input, 0, 0.0, None, Some(1)),
+            Node::new(
+                NeuronType::Output,
+                1,
+                0.0,
+                Some(Activation::Identity),
+                Some(2),
+            ),
+        ];
+        let mut connection = Connection::new(0, 1, 0.5);
+        connection.set_hebbian(Some(Hebbian {
+            a: 1.0,
+            b: 0.0,
+            c: 0.0,
+            d: 0.0,
+            learning_rate: 0.5,
+        }));
+
+        Organism::new(Genome::new(nodes, vec![connection]).expect("can't create genome"))
+    }
+
+    #[test]
+    fn activate_sequence() {
+        let organism = organism();
+        let inputs = Array2::from_elem((3, 1), 1.0);
+
+        let outputs = organism.activate_sequence(&inputs);
+        assert_eq!(outputs.column(0).to_vec(), vec![0.5, 0.75, 1.125]);
+
+        let sequence = Sequence::new(inputs, outputs).with_metric(Metric::Mae);
+        assert_eq!(sequence.calculate(&organism), 1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "sliding window is empty")]
+    fn sliding_window_empty() {
+        let series = Array2::from_shape_vec((3, 1), vec![1.0, 2.0, 3.0]).expect("");
+
+        Sequence::sliding_window(&series, 0, 0);
+    }
+
+    #[test]
+    fn next_step_short() {
+        for rows in [0, 1] {
+            let series = Array2::zeros((rows, 2));
+
+            let sequence = Sequence::next_step(&series);
+            assert_eq!(sequence.len(), 0);
+            assert_eq!(sequence.get_inputs().ncols(), 2);
+        }
+    }
+
+    #[test]
+    fn sliding_window() {
+        let series = Array2::from_shape_vec((5, 1), vec![1.0, 2.0, 3.0, 4.0, 5.0]).expect("");
+
+        let sequence = Sequence::sliding_window(&series, 2, 1);
+        assert_eq!(
+            sequence.get_inputs().iter().copied().collect::<Vec<_>>(),
+            vec![1.0, 2.0, 2.0, 3.0, 3.0, 4.0]
+        );
+        assert_eq!(
+            sequence.get_targets().column(0).to_vec(),
+            vec![3.0, 4.0, 5.0]
+        );
+
+        let sequence = Sequence::next_step(&series);
+        assert_eq!(sequence.len(), 4);
+        assert_eq!(
+            sequence.get_targets().column(0).to_vec(),
+            vec![2.0, 3.0, 4.0, 5.0]
+        );
+    }
+}
@@ -1,3 +1,13 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use vivalaakam_neuro_utils::config_file;
+use vivalaakam_neuro_utils::ConfigError;
+
+/// Missing fields take their default values when deserialized.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Config {
     pub crossover_chance: f32,
     pub mutation_chance: f32,
@@ -5,3 +15,54 @@ pub struct Config {
     pub learning_coeff: f32,
     pub learning_chance: f32,
 }
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            crossover_chance: 0.5,
+            mutation_chance: 0.1,
+            mutation_coeff: 0.5,
+            learning_coeff: 0.1,
+            learning_chance: 0.1,
+        }
+    }
+}
+
+impl Config {
+    pub fn from_toml(data: &str) -> Result<Self, ConfigError> {
+        config_file::from_toml(data)
+    }
+
+    pub fn from_json(data: &str) -> Result<Self, ConfigError> {
+        config_file::from_json(data)
+    }
+
+    pub fn from_file<P>(path: P) -> Result<Self, ConfigError>
+    where
+        P: AsRef<Path>,
+    {
+        config_file::from_file(path.as_ref())
+    }
+
+    /// Overrides fields from `NN_<FIELD>` environment variables, e.g. `NN_MUTATION_CHANCE=0.2`.
+    pub fn with_env(self) -> Result<Self, ConfigError> {
+        config_file::with_env(self, "NN_")
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        for (field, value) in [
+            ("crossover_chance", self.crossover_chance),
+            ("mutation_chance", self.mutation_chance),
+            ("learning_chance", self.learning_chance),
+        ] {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(ConfigError::Probability {
+                    field: field.to_string(),
+                    value,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,6 +1,8 @@
 pub use self::agent::*;
 pub use self::config::Config;
+pub use self::layer::Layer;
 pub use self::neural_network::*;
+pub use self::neuron::Neuron;
 
 mod agent;
 mod config;
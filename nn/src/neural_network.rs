@@ -1,3 +1,4 @@
+use ndarray::{Array1, Array2};
 use rand::Rng;
 use sha2::{Digest, Sha256};
 
@@ -21,6 +22,22 @@ impl NeuralNetwork {
             .fold(inputs, |inputs, layer| layer.activate(inputs))
     }
 
+    pub fn activate_sequence(&self, sequence: &Array2<f32>) -> Array2<f32> {
+        let outputs = self
+            .layers
+            .last()
+            .map_or(sequence.ncols(), |layer| layer.neurons.len());
+        let mut result = Array2::zeros((sequence.nrows(), outputs));
+
+        for (i, row) in sequence.rows().into_iter().enumerate() {
+            result
+                .row_mut(i)
+                .assign(&Array1::from(self.activate(row.to_vec())));
+        }
+
+        result
+    }
+
     pub fn get_weights(&self) -> Vec<f32> {
         self.layers
             .iter()
@@ -157,4 +174,20 @@ mod tests {
 
         assert_eq!(nn.get_topology(), topology);
     }
+
+    #[test]
+    fn test_activate_sequence() {
+        let mut rng = rand::rng();
+
+        let nn = NeuralNetwork::random(&mut rng, &[2, 1, 2, 2, 3, 1, 2, 1]);
+        let sequence =
+            Array2::from_shape_vec((3, 2), vec![0.0, 1.0, 0.5, -0.5, 1.0, 0.0]).expect("");
+
+        let outputs = nn.activate_sequence(&sequence);
+
+        assert_eq!(outputs.dim(), (3, 2));
+        for (row, output) in sequence.rows().into_iter().zip(outputs.rows()) {
+            assert_eq!(output.to_vec(), nn.activate(row.to_vec()));
+        }
+    }
 }
@@ -0,0 +1,82 @@
+use vivalaakam_neuro_utils::random::get_random_weight;
+
+use crate::environment::{Environment, Step};
+
+const GRAVITY: f32 = 9.8;
+const CART_MASS: f32 = 1.0;
+const POLE_MASS: f32 = 0.1;
+const POLE_HALF_LENGTH: f32 = 0.5;
+const FORCE: f32 = 10.0;
+const TAU: f32 = 0.02;
+const X_LIMIT: f32 = 2.4;
+const THETA_LIMIT: f32 = 12.0 * std::f32::consts::PI / 180.0;
+
+/// Classic cart-pole balancing (Barto, Sutton and Anderson).
+///
+/// Observation: `[x, x_dot, theta, theta_dot]`. Action: one value, the cart is
+/// pushed right above 0.5 and left otherwise. Reward is 1 per step; the episode
+/// ends when the cart leaves the track or the pole falls past 12 degrees.
+#[derive(Debug, Clone, Default)]
+pub struct CartPole {
+    state: [f32; 4],
+}
+
+impl CartPole {
+    pub fn new() -> Self {
+        CartPole::default()
+    }
+
+    pub fn get_state(&self) -> [f32; 4] {
+        self.state
+    }
+}
+
+impl Environment for CartPole {
+    fn reset(&mut self) -> Vec<f32> {
+        self.state = [
+            get_random_weight(0.05),
+            get_random_weight(0.05),
+            get_random_weight(0.05),
+            get_random_weight(0.05),
+        ];
+
+        self.state.to_vec()
+    }
+
+    fn step(&mut self, action: &[f32]) -> Step {
+        let [x, x_dot, theta, theta_dot] = self.state;
+        let force = if action.first().copied().unwrap_or_default() > 0.5 {
+            FORCE
+        } else {
+            -FORCE
+        };
+
+        let total_mass = CART_MASS + POLE_MASS;
+        let (sin, cos) = theta.sin_cos();
+        let temp = (force + POLE_MASS * POLE_HALF_LENGTH * theta_dot.powi(2) * sin) / total_mass;
+        let theta_acc = (GRAVITY * sin - cos * temp)
+            / (POLE_HALF_LENGTH * (4.0 / 3.0 - POLE_MASS * cos.powi(2) / total_mass));
+        let x_acc = temp - POLE_MASS * POLE_HALF_LENGTH * theta_acc * cos / total_mass;
+
+        self.state = [
+            x + TAU * x_dot,
+            x_dot + TAU * x_acc,
+            theta + TAU * theta_dot,
+            theta_dot + TAU * theta_acc,
+        ];
+
+        Step {
+            observation: self.state.to_vec(),
+            reward: 1.0,
+            done: self.state[0].abs() > X_LIMIT || self.state[2].abs() > THETA_LIMIT,
+        }
+    }
+
+    fn get_observation_size(&self) -> usize {
+        4
+    }
+
+    fn get_action_size(&self) -> usize {
+        1
+    }
+}
@@ -0,0 +1,246 @@
+use std::collections::HashSet;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::Path;
+
+use anyhow::{anyhow, ensure, Context};
+use ndarray::{Array2, Axis};
+
+use vivalaakam_neuro_utils::random::get_random_position;
+
+use crate::fitness::FitnessTrait;
+use crate::metric::Metric;
+use crate::organism::OrganismTrait;
+
+/// Supervised dataset: input and target rows, scored with a `Metric`.
+#[derive(Debug, Clone)]
+pub struct Dataset {
+    inputs: Array2<f32>,
+    targets: Array2<f32>,
+    metric: Metric,
+    fingerprint: u64,
+}
+
+impl Dataset {
+    /// Creates a dataset from inputs and targets with the same number of rows.
+    pub fn new(inputs: Array2<f32>, targets: Array2<f32>) -> anyhow::Result<Self> {
+        ensure!(
+            inputs.nrows() == targets.nrows(),
+            "inputs and targets have different lengths: {} and {}",
+            inputs.nrows(),
+            targets.nrows()
+        );
+
+        Ok(Dataset::from_rows(inputs, targets, Metric::default()))
+    }
+
+    fn from_rows(inputs: Array2<f32>, targets: Array2<f32>, metric: Metric) -> Self {
+        let mut hasher = DefaultHasher::new();
+
+        inputs.shape().hash(&mut hasher);
+        targets.shape().hash(&mut hasher);
+
+        for value in inputs.iter().chain(targets.iter()) {
+            value.to_bits().hash(&mut hasher);
+        }
+
+        Dataset {
+            inputs,
+            targets,
+            metric,
+            fingerprint: hasher.finish(),
+        }
+    }
+
+    /// Loads a CSV file with a header, taking input and target columns by name.
+    pub fn from_csv<P>(path: P, inputs: &[&str], targets: &[&str]) -> anyhow::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let mut reader = csv::Reader::from_path(path)
+            .with_context(|| format!("can't open {}", path.display()))?;
+
+        let headers = reader.headers()?.clone();
+        let position = |name: &&str| {
+            headers
+                .iter()
+                .position(|header| header == *name)
+                .ok_or_else(|| anyhow!("column {name} not found in {}", path.display()))
+        };
+
+        let input_columns = inputs
+            .iter()
+            .map(position)
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let target_columns = targets
+            .iter()
+            .map(position)
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let mut input_values = vec![];
+        let mut target_values = vec![];
+        let mut rows = 0;
+
+        for (line, record) in reader.records().enumerate() {
+            let record = record?;
+
+            let parse =
+                |columns: &[usize], values: &mut Vec<f32>| -> anyhow::Result<()> {
+                    for column in columns {
+                        let value = record.get(*column).unwrap_or_default().trim();
+                        values.push(value.parse::<f32>().with_context(|| {
+                            format!("can't parse {value:?} at row {}", line + 1)
+                        })?);
+                    }
+                    Ok(())
+                };
+
+            parse(&input_columns, &mut input_values)?;
+            parse(&target_columns, &mut target_values)?;
+            rows += 1;
+        }
+
+        Dataset::new(
+            Array2::from_shape_vec((rows, inputs.len()), input_values)?,
+            Array2::from_shape_vec((rows, targets.len()), target_values)?,
+        )
+    }
+
+    /// Sets the metric used as fitness.
+    pub fn with_metric(self, metric: Metric) -> Self {
+        Dataset { metric, ..self }
+    }
+
+    pub fn get_inputs(&self) -> &Array2<f32> {
+        &self.inputs
+    }
+
+    pub fn get_targets(&self) -> &Array2<f32> {
+        &self.targets
+    }
+
+    pub fn get_metric(&self) -> Metric {
+        self.metric
+    }
+
+    pub fn len(&self) -> usize {
+        self.inputs.nrows()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inputs.nrows() == 0
+    }
+
+    /// Returns a dataset with the given rows, in the given order.
+    pub fn select(&self, rows: &[usize]) -> Self {
+        Dataset::from_rows(
+            self.inputs.select(Axis(0), rows),
+            self.targets.select(Axis(0), rows),
+            self.metric,
+        )
+    }
+
+    /// Returns a dataset with the rows in random order.
+    pub fn shuffle(&self) -> Self {
+        self.sample(self.len())
+    }
+
+    /// Returns `size` distinct random rows in random order, allocating only `size` indices.
+    pub fn sample(&self, size: usize) -> Self {
+        let size = size.min(self.len());
+        let mut picked = HashSet::with_capacity(size);
+        let mut rows = Vec::with_capacity(size);
+
+        for last in self.len() - size..self.len() {
+            let mut row = get_random_position(last + 1);
+
+            if !picked.insert(row) {
+                row = last;
+                picked.insert(row);
+            }
+
+            rows.push(row);
+        }
+
+        for i in (1..rows.len()).rev() {
+            rows.swap(i, get_random_position(i + 1));
+        }
+
+        self.select(&rows)
+    }
+
+    /// Shuffles the rows and splits them into train, validation and test datasets.
+    ///
+    /// `validation` and `test` are the shares of rows, clamped to `[0, 1]`; the rest is for
+    /// training. Validation rows are taken first when the shares add up to more than one.
+    pub fn split(&self, validation: f32, test: f32) -> (Dataset, Dataset, Dataset) {
+        let shuffled = self.shuffle();
+        let validation = (self.len() as f32 * validation.clamp(0.0, 1.0)).round() as usize;
+        let test = ((self.len() as f32 * test.clamp(0.0, 1.0)).round() as usize)
+            .min(self.len() - validation);
+        let train = self.len() - validation - test;
+
+        let rows = (0..self.len()).collect::<Vec<_>>();
+
+        (
+            shuffled.select(&rows[..train]),
+            shuffled.select(&rows[train..train + validation]),
+            shuffled.select(&rows[train + validation..]),
+        )
+    }
+
+    /// Splits the rows into `k` consecutive folds and returns `(train, validation)` pairs,
+    /// one per fold. Shuffle the dataset first if the rows are ordered.
+    pub fn k_folds(&self, k: usize) -> Vec<(Dataset, Dataset)> {
+        self.folds(k)
+            .iter()
+            .map(|validation| {
+                let train = (0..self.len())
+                    .filter(|row| !validation.contains(row))
+                    .collect::<Vec<_>>();
+
+                (self.select(&train), self.select(validation))
+            })
+            .collect()
+    }
+
+    fn folds(&self, k: usize) -> Vec<Vec<usize>> {
+        assert!(k > 0 && k <= self.len(), "k must be in 1..=len");
+
+        (0..k)
+            .map(|fold| (fold * self.len() / k..(fold + 1) * self.len() / k).collect())
+            .collect()
+    }
+
+    /// Returns the raw metric value of the organism on this dataset.
+    pub fn evaluate<T, C>(&self, organism: &T) -> f32
+    where
+        T: OrganismTrait<C>,
+    {
+        self.metric.evaluate(
+            organism.activate_matrix(&self.inputs).view(),
+            self.targets.view(),
+        )
+    }
+}
+
+impl FitnessTrait for Dataset {
+    fn calculate<T, C>(&self, organism: &T) -> f32
+    where
+        T: OrganismTrait<C>,
+    {
+        self.metric.fitness(
+            organism.activate_matrix(&self.inputs).view(),
+            self.targets.view(),
+        )
+    }
+
+    fn get_fingerprint(&self) -> Option<u64> {
+        let mut hasher = DefaultHasher::new();
+
+        self.fingerprint.hash(&mut hasher);
+        format!("{:?}", self.metric).hash(&mut hasher);
+
+        Some(hasher.finish())
+    }
+}
@@ -0,0 +1,116 @@
+use crate::environment::{Environment, Step};
+
+const GRAVITY: f32 = -9.8;
+const CART_MASS: f32 = 1.0;
+const POLE_MASSES: [f32; 2] = [0.1, 0.01];
+const POLE_HALF_LENGTHS: [f32; 2] = [0.5, 0.05];
+const CART_FRICTION: f32 = 0.0005;
+const POLE_FRICTION: f32 = 0.000002;
+const FORCE: f32 = 10.0;
+const TAU: f32 = 0.01;
+const X_LIMIT: f32 = 2.4;
+const THETA_LIMIT: f32 = 36.0 * std::f32::consts::PI / 180.0;
+const INITIAL_THETA: f32 = 4.5 * std::f32::consts::PI / 180.0;
+
+/// Double-pole balancing with velocities (Wieland), integrated with Runge-Kutta.
+///
+/// Observation: `[x, x_dot, theta1, theta1_dot, theta2, theta2_dot]`. Action: one
+/// value in `[0, 1]` mapped to a force in `[-10, 10]` N. Reward is 1 per step; the
+/// episode ends when the cart leaves the track or a pole falls past 36 degrees.
+/// The long pole starts tilted by 4.5 degrees.
+#[derive(Debug, Clone, Default)]
+pub struct DoublePole {
+    state: [f32; 6],
+}
+
+impl DoublePole {
+    pub fn new() -> Self {
+        DoublePole::default()
+    }
+
+    pub fn get_state(&self) -> [f32; 6] {
+        self.state
+    }
+
+    fn derivative(state: &[f32; 6], force: f32) -> [f32; 6] {
+        let mut forces = 0f32;
+        let mut masses = 0f32;
+
+        for pole in 0..2 {
+            let (theta, theta_dot) = (state[2 + pole * 2], state[3 + pole * 2]);
+            let ml = POLE_MASSES[pole] * POLE_HALF_LENGTHS[pole];
+            let (sin, cos) = theta.sin_cos();
+
+            forces += ml * theta_dot.powi(2) * sin
+                + 0.75 * POLE_MASSES[pole] * cos * (POLE_FRICTION * theta_dot / ml + GRAVITY * sin);
+            masses += POLE_MASSES[pole] * (1.0 - 0.75 * cos.powi(2));
+        }
+
+        let x_acc = (force - CART_FRICTION * state[1].signum() + forces) / (CART_MASS + masses);
+
+        let mut derivative = [state[1], x_acc, 0.0, 0.0, 0.0, 0.0];
+
+        for pole in 0..2 {
+            let (theta, theta_dot) = (state[2 + pole * 2], state[3 + pole * 2]);
+            let ml = POLE_MASSES[pole] * POLE_HALF_LENGTHS[pole];
+            let (sin, cos) = theta.sin_cos();
+
+            derivative[2 + pole * 2] = theta_dot;
+            derivative[3 + pole * 2] = -0.75
+                * (x_acc * cos + GRAVITY * sin + POLE_FRICTION * theta_dot / ml)
+                / POLE_HALF_LENGTHS[pole];
+        }
+
+        derivative
+    }
+
+    fn integrate(&mut self, force: f32) {
+        let shift = |state: &[f32; 6], derivative: &[f32; 6], scale: f32| {
+            let mut result = *state;
+            for i in 0..6 {
+                result[i] += derivative[i] * scale;
+            }
+            result
+        };
+
+        let k1 = DoublePole::derivative(&self.state, force);
+        let k2 = DoublePole::derivative(&shift(&self.state, &k1, TAU / 2.0), force);
+        let k3 = DoublePole::derivative(&shift(&self.state, &k2, TAU / 2.0), force);
+        let k4 = DoublePole::derivative(&shift(&self.state, &k3, TAU), force);
+
+        for i in 0..6 {
+            self.state[i] += TAU / 6.0 * (k1[i] + 2.0 * k2[i] + 2.0 * k3[i] + k4[i]);
+        }
+    }
+}
+
+impl Environment for DoublePole {
+    fn reset(&mut self) -> Vec<f32> {
+        self.state = [0.0, 0.0, INITIAL_THETA, 0.0, 0.0, 0.0];
+        self.state.to_vec()
+    }
+
+    fn step(&mut self, action: &[f32]) -> Step {
+        let action = action.first().copied().unwrap_or(0.5).clamp(0.0, 1.0);
+        let force = (action * 2.0 - 1.0) * FORCE;
+
+        self.integrate(force);
+        self.integrate(force);
+
+        Step {
+            observation: self.state.to_vec(),
+            reward: 1.0,
+            done: self.state[0].abs() > X_LIMIT
+                || self.state[2].abs() > THETA_LIMIT
+                || self.state[4].abs() > THETA_LIMIT,
+        }
+    }
+
+    fn get_observation_size(&self) -> usize {
+        6
+    }
+
+    fn get_action_size(&self) -> usize {
+        1
+    }
+}
@@ -0,0 +1,19 @@
+/// Result of one environment step.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    pub observation: Vec<f32>,
+    pub reward: f32,
+    pub done: bool,
+}
+
+/// Episodic control task driven by organism outputs.
+pub trait Environment {
+    /// Starts a new episode and returns the first observation.
+    fn reset(&mut self) -> Vec<f32>;
+    /// Applies the action (organism outputs) and advances the environment.
+    fn step(&mut self, action: &[f32]) -> Step;
+    /// Returns the number of observation values (organism inputs).
+    fn get_observation_size(&self) -> usize;
+    /// Returns the number of action values (organism outputs).
+    fn get_action_size(&self) -> usize;
+}
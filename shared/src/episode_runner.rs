@@ -0,0 +1,99 @@
+use std::sync::Mutex;
+
+use crate::environment::Environment;
+use crate::evaluator::{EvaluatorTrait, FitnessReport};
+use crate::fitness::FitnessTrait;
+use crate::organism::OrganismTrait;
+
+/// Runs organisms through episodes of an environment; the fitness is the mean return.
+pub struct EpisodeRunner<E> {
+    environment: Mutex<E>,
+    episodes: usize,
+    max_steps: usize,
+}
+
+impl<E> EpisodeRunner<E>
+where
+    E: Environment,
+{
+    pub fn new(environment: E, episodes: usize, max_steps: usize) -> Self {
+        EpisodeRunner {
+            environment: Mutex::new(environment),
+            episodes,
+            max_steps,
+        }
+    }
+
+    pub fn get_episodes(&self) -> usize {
+        self.episodes
+    }
+
+    pub fn get_max_steps(&self) -> usize {
+        self.max_steps
+    }
+
+    /// Runs a single episode and returns its return and number of steps.
+    pub fn run_episode<T, C>(&self, organism: &T) -> (f32, usize)
+    where
+        T: OrganismTrait<C>,
+    {
+        let mut environment = self.environment.lock().unwrap();
+
+        organism.reset();
+        let mut observation = environment.reset();
+        let mut total = 0f32;
+
+        for step in 0..self.max_steps {
+            let result = environment.step(&organism.activate(observation));
+            total += result.reward;
+
+            if result.done {
+                return (total, step + 1);
+            }
+
+            observation = result.observation;
+        }
+
+        (total, self.max_steps)
+    }
+
+    /// Runs every episode and returns the mean return and the mean number of steps.
+    pub fn run<T, C>(&self, organism: &T) -> (f32, f32)
+    where
+        T: OrganismTrait<C>,
+    {
+        let (total, steps) = (0..self.episodes)
+            .map(|_| self.run_episode(organism))
+            .fold((0f32, 0usize), |(total, steps), (r, s)| {
+                (total + r, steps + s)
+            });
+
+        let episodes = self.episodes.max(1) as f32;
+
+        (total / episodes, steps as f32 / episodes)
+    }
+}
+
+impl<E> FitnessTrait for EpisodeRunner<E>
+where
+    E: Environment,
+{
+    fn calculate<T, C>(&self, organism: &T) -> f32
+    where
+        T: OrganismTrait<C>,
+    {
+        self.run(organism).0
+    }
+}
+
+impl<E, T, C> EvaluatorTrait<T, C> for EpisodeRunner<E>
+where
+    E: Environment,
+    T: OrganismTrait<C>,
+{
+    fn evaluate(&mut self, organism: &T) -> anyhow::Result<FitnessReport> {
+        let (score, steps) = self.run(organism);
+
+        Ok(FitnessReport::new(score).with_diagnostic("steps", steps))
+    }
+}
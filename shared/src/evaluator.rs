@@ -0,0 +1,104 @@
+use std::collections::BTreeMap;
+
+use crate::fitness::FitnessTrait;
+use crate::organism::OrganismTrait;
+
+/// Result of evaluating an organism.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FitnessReport {
+    /// Scalar fitness used for selection, higher is better.
+    pub score: f32,
+    /// Separate scores for multi-objective selection.
+    pub objectives: Option<Vec<f32>>,
+    /// Behavior descriptor, e.g. for novelty search.
+    pub behavior: Option<Vec<f32>>,
+    /// Named values for logging (steps survived, loss components, ...).
+    pub diagnostics: BTreeMap<String, f32>,
+}
+
+impl FitnessReport {
+    pub fn new(score: f32) -> Self {
+        FitnessReport {
+            score,
+            ..FitnessReport::default()
+        }
+    }
+
+    pub fn with_objectives(self, objectives: Vec<f32>) -> Self {
+        FitnessReport {
+            objectives: Some(objectives),
+            ..self
+        }
+    }
+
+    pub fn with_behavior(self, behavior: Vec<f32>) -> Self {
+        FitnessReport {
+            behavior: Some(behavior),
+            ..self
+        }
+    }
+
+    pub fn with_diagnostic(mut self, name: &str, value: f32) -> Self {
+        self.diagnostics.insert(name.to_string(), value);
+        self
+    }
+}
+
+impl From<f32> for FitnessReport {
+    fn from(score: f32) -> Self {
+        FitnessReport::new(score)
+    }
+}
+
+/// Evaluates organisms of type `T`; may keep state between evaluations and may fail.
+pub trait EvaluatorTrait<T, C>
+where
+    T: OrganismTrait<C>,
+{
+    fn evaluate(&mut self, organism: &T) -> anyhow::Result<FitnessReport>;
+
+    /// See `FitnessTrait::get_fingerprint`; without one the fitness cache is skipped.
+    fn get_fingerprint(&self) -> Option<u64> {
+        None
+    }
+}
+
+/// Evaluates many organisms at once, e.g. by spreading them over remote workers.
+pub trait BatchEvaluatorTrait<T> {
+    /// Returns one fitness per organism, in the same order.
+    fn evaluate_batch(&mut self, organisms: &[&T]) -> anyhow::Result<Vec<f32>>;
+
+    /// See `FitnessTrait::get_fingerprint`; without one the fitness cache is skipped.
+    fn get_fingerprint(&self) -> Option<u64> {
+        None
+    }
+}
+
+/// Adaptor running a `FitnessTrait` as an `EvaluatorTrait`.
+pub struct Stateless<F> {
+    fitness: F,
+}
+
+impl<F> Stateless<F> {
+    pub fn new(fitness: F) -> Self {
+        Stateless { fitness }
+    }
+
+    pub fn get_fitness(&self) -> &F {
+        &self.fitness
+    }
+}
+
+impl<F, T, C> EvaluatorTrait<T, C> for Stateless<F>
+where
+    F: FitnessTrait,
+    T: OrganismTrait<C>,
+{
+    fn evaluate(&mut self, organism: &T) -> anyhow::Result<FitnessReport> {
+        Ok(FitnessReport::new(self.fitness.calculate(organism)))
+    }
+
+    fn get_fingerprint(&self) -> Option<u64> {
+        self.fitness.get_fingerprint()
+    }
+}
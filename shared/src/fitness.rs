@@ -1,7 +1,31 @@
+use std::future::Future;
+
 use crate::organism::OrganismTrait;
 
 pub trait FitnessTrait {
     fn calculate<T, C>(&self, organism: &T) -> f32
     where
         T: OrganismTrait<C>;
+
+    /// Identifies the data behind the fitness; a change invalidates cached fitness values
+    /// and without one the fitness cache is skipped.
+    fn get_fingerprint(&self) -> Option<u64> {
+        None
+    }
+}
+
+/// Fitness computed asynchronously, e.g. by a simulator behind async I/O.
+///
+/// The future only borrows the organism; `Population::epoch_async` drives many of
+/// them at once on whatever executor awaits it. The future is `Send`, so the epoch
+/// can run on a multi-threaded runtime.
+pub trait AsyncFitnessTrait {
+    fn calculate<T, C>(&self, organism: &T) -> impl Future<Output = f32> + Send
+    where
+        T: OrganismTrait<C> + Sync;
+
+    /// See `FitnessTrait::get_fingerprint`.
+    fn get_fingerprint(&self) -> Option<u64> {
+        None
+    }
 }
@@ -0,0 +1,121 @@
+use std::num::NonZeroUsize;
+
+use lru::LruCache;
+
+use crate::evaluator::FitnessReport;
+use crate::organism::OrganismTrait;
+
+/// Least recently used fitness values keyed by `OrganismTrait::get_hash`, stored with
+/// the organism's `FitnessReport` when it has one.
+///
+/// Only valid for deterministic fitness functions with a fingerprint; without one
+/// the cache is skipped. The cache is cleared when the fingerprint changes, or
+/// explicitly with `invalidate`.
+pub struct FitnessCache {
+    entries: LruCache<String, (f32, Option<FitnessReport>)>,
+    fingerprint: Option<u64>,
+    hits: usize,
+    misses: usize,
+}
+
+impl FitnessCache {
+    /// Creates a cache holding up to `capacity` fitness values (at least one).
+    pub fn new(capacity: usize) -> Self {
+        FitnessCache {
+            entries: LruCache::new(NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN)),
+            fingerprint: None,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Returns the cached fitness for a hash, marking it as recently used.
+    pub fn get(&mut self, hash: &str) -> Option<f32> {
+        self.entry(hash).map(|(fitness, _)| fitness)
+    }
+
+    /// Stores the fitness for a hash, evicting the least recently used entry when full.
+    pub fn put(&mut self, hash: String, fitness: f32) {
+        self.entries.put(hash, (fitness, None));
+    }
+
+    fn entry(&mut self, hash: &str) -> Option<(f32, Option<FitnessReport>)> {
+        let entry = self.entries.get(hash).cloned();
+
+        if entry.is_some() {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+        }
+
+        entry
+    }
+
+    /// Drops every cached fitness.
+    pub fn invalidate(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Invalidates the cache when the fingerprint differs from the last one seen.
+    ///
+    /// Returns false when there is no fingerprint and the cache must not be used.
+    pub fn check_fingerprint(&mut self, fingerprint: Option<u64>) -> bool {
+        if fingerprint.is_none() {
+            return false;
+        }
+
+        if fingerprint != self.fingerprint {
+            self.invalidate();
+            self.fingerprint = fingerprint;
+        }
+
+        true
+    }
+
+    /// Sets the fitness and report of an organism from the cache; returns false on a miss.
+    pub fn lookup<T, C>(&mut self, organism: &T) -> bool
+    where
+        T: OrganismTrait<C>,
+    {
+        let Some((fitness, report)) = organism.get_hash().and_then(|hash| self.entry(&hash)) else {
+            return false;
+        };
+
+        if let Some(report) = report {
+            organism.set_report(report);
+        }
+
+        organism.set_fitness(fitness);
+
+        true
+    }
+
+    /// Caches the current fitness and report of an organism.
+    pub fn store<T, C>(&mut self, organism: &T)
+    where
+        T: OrganismTrait<C>,
+    {
+        if let Some(hash) = organism.get_hash() {
+            self.entries
+                .put(hash, (organism.get_fitness(), organism.get_report()));
+        }
+    }
+
+    /// Returns the number of lookups answered from the cache.
+    pub fn get_hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Returns the number of lookups that needed an evaluation.
+    pub fn get_misses(&self) -> usize {
+        self.misses
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
@@ -0,0 +1,189 @@
+use std::thread;
+
+use crate::fitness::FitnessTrait;
+use crate::organism::OrganismTrait;
+use crate::population::Population;
+use crate::reporter::{Reporter, Reporters};
+
+/// Directions in which organisms migrate between islands.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Topology {
+    /// Island `i` sends its migrants to island `i + 1`, the last one to the first.
+    #[default]
+    Ring,
+    /// Every island sends its migrants to every other island.
+    FullyConnected,
+}
+
+impl Topology {
+    /// Returns the islands receiving migrants from island `from` out of `count`.
+    pub fn targets(&self, from: usize, count: usize) -> Vec<usize> {
+        match self {
+            _ if count < 2 => vec![],
+            Topology::Ring => vec![(from + 1) % count],
+            Topology::FullyConnected => (0..count).filter(|to| *to != from).collect(),
+        }
+    }
+}
+
+/// Island model: populations evolving in parallel, exchanging their best organisms.
+///
+/// Every island is a `Population` with its own `Config`; each epoch runs the islands
+/// on separate threads. Every `interval` epochs the best `migrants` organisms of each
+/// island are copied along the `Topology` and replace the worst organisms of the
+/// receiving island. Reporters added to `Islands` see all islands as one population.
+pub struct Islands<C, T> {
+    islands: Vec<Population<C, T>>,
+    topology: Topology,
+    interval: usize,
+    migrants: usize,
+    epoch: usize,
+    reporters: Reporters<T>,
+}
+
+impl<C, T> Islands<C, T>
+where
+    C: Clone + Default + Send,
+    T: OrganismTrait<C> + std::cmp::Ord + Clone + Send + Sync,
+{
+    /// Creates islands migrating one organism every 10 epochs.
+    pub fn new(islands: Vec<Population<C, T>>, topology: Topology) -> Self {
+        Islands {
+            islands,
+            topology,
+            interval: 10,
+            migrants: 1,
+            epoch: 0,
+            reporters: Reporters::new(),
+        }
+    }
+
+    /// Sets the number of epochs between migrations.
+    pub fn with_interval(mut self, interval: usize) -> Self {
+        self.interval = interval.max(1);
+        self
+    }
+
+    /// Sets the number of organisms each island sends per migration.
+    pub fn with_migrants(mut self, migrants: usize) -> Self {
+        self.migrants = migrants;
+        self
+    }
+
+    /// Adds a reporter called with the organisms of all islands.
+    pub fn add_reporter(&mut self, reporter: Box<dyn Reporter<T>>) {
+        self.reporters.add(reporter);
+    }
+
+    pub fn get_islands(&self) -> &[Population<C, T>] {
+        &self.islands
+    }
+
+    pub fn get_islands_mut(&mut self) -> &mut [Population<C, T>] {
+        &mut self.islands
+    }
+
+    pub fn get_topology(&self) -> Topology {
+        self.topology
+    }
+
+    /// Returns the number of finished epochs.
+    pub fn get_epoch(&self) -> usize {
+        self.epoch
+    }
+
+    /// Returns the best organism over all islands.
+    pub fn get_best(&self) -> Option<&T> {
+        self.islands
+            .iter()
+            .filter_map(|island| island.get_organisms().first())
+            .min()
+    }
+
+    /// Scores the organisms of every island in parallel.
+    pub fn calculate_fitness<D>(&mut self, dataset: &D)
+    where
+        D: FitnessTrait + Sync,
+    {
+        thread::scope(|scope| {
+            for island in self.islands.iter_mut() {
+                scope.spawn(move || island.calculate_fitness(dataset));
+            }
+        });
+    }
+
+    /// Runs an epoch on every island in parallel, then migrates when due.
+    pub fn epoch<D>(&mut self, dataset: &D) -> Option<&T>
+    where
+        D: FitnessTrait + Sync,
+    {
+        self.reporters.epoch_start(self.epoch);
+
+        thread::scope(|scope| {
+            for island in self.islands.iter_mut() {
+                scope.spawn(move || {
+                    island.epoch(dataset);
+                });
+            }
+        });
+
+        self.epoch += 1;
+
+        if self.epoch.is_multiple_of(self.interval) {
+            self.migrate();
+        }
+
+        if !self.reporters.is_empty() {
+            let mut organisms = self
+                .islands
+                .iter()
+                .flat_map(|island| island.get_organisms().iter().cloned())
+                .collect::<Vec<_>>();
+            organisms.sort();
+
+            self.reporters.epoch_end(self.epoch - 1, &organisms);
+        }
+
+        self.get_best()
+    }
+
+    /// Copies the best `migrants` organisms of every island to its `Topology` targets.
+    pub fn migrate(&mut self) {
+        let count = self.islands.len();
+        let mut incoming: Vec<Vec<T>> = vec![vec![]; count];
+
+        for (from, island) in self.islands.iter().enumerate() {
+            let migrants = island
+                .get_organisms()
+                .iter()
+                .take(self.migrants)
+                .cloned()
+                .collect::<Vec<_>>();
+
+            for to in self.topology.targets(from, count) {
+                incoming[to].extend(migrants.iter().cloned());
+            }
+        }
+
+        for (island, organisms) in self.islands.iter_mut().zip(incoming) {
+            if !organisms.is_empty() {
+                island.immigrate(organisms);
+            }
+        }
+    }
+
+    /// Calls `run_end` on the reporters of `Islands` and of every island.
+    pub fn finish(&mut self) {
+        for island in self.islands.iter_mut() {
+            island.finish();
+        }
+
+        let best = self
+            .islands
+            .iter()
+            .filter_map(|island| island.get_organisms().first())
+            .min();
+
+        self.reporters.run_end(self.epoch, best);
+    }
+}
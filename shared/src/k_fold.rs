@@ -0,0 +1,86 @@
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use crate::dataset::Dataset;
+use crate::fitness::FitnessTrait;
+use crate::organism::OrganismTrait;
+
+/// K-fold cross-validation over a dataset.
+///
+/// As a fitness, `calculate` is the mean fitness over the held-out rows of every fold.
+/// For a single-fold run, evolve on `get_train` and score on `get_validation` of the
+/// active fold, and keep that fold for the whole run: `next_fold` changes the rows the
+/// population was scored on.
+#[derive(Debug, Clone)]
+pub struct KFold {
+    folds: Vec<(Dataset, Dataset)>,
+    fold: usize,
+}
+
+impl KFold {
+    /// Splits the dataset into `k` consecutive folds; the first one is active.
+    pub fn new(dataset: &Dataset, k: usize) -> Self {
+        KFold {
+            folds: dataset.k_folds(k),
+            fold: 0,
+        }
+    }
+
+    /// Returns the `(train, validation)` pairs of every fold.
+    pub fn get_folds(&self) -> &[(Dataset, Dataset)] {
+        &self.folds
+    }
+
+    /// Returns the index of the active fold.
+    pub fn get_fold(&self) -> usize {
+        self.fold
+    }
+
+    /// Returns the train rows of the active fold.
+    pub fn get_train(&self) -> &Dataset {
+        &self.folds[self.fold].0
+    }
+
+    /// Returns the held-out rows of the active fold.
+    pub fn get_validation(&self) -> &Dataset {
+        &self.folds[self.fold].1
+    }
+
+    /// Activates the next fold and returns its train rows.
+    pub fn next_fold(&mut self) -> &Dataset {
+        self.fold = (self.fold + 1) % self.folds.len();
+        self.get_train()
+    }
+
+    /// Returns the fitness of the organism on the held-out rows of every fold.
+    pub fn calculate_folds<T, C>(&self, organism: &T) -> Vec<f32>
+    where
+        T: OrganismTrait<C>,
+    {
+        self.folds
+            .iter()
+            .map(|(_, validation)| validation.calculate(organism))
+            .collect()
+    }
+}
+
+impl FitnessTrait for KFold {
+    fn calculate<T, C>(&self, organism: &T) -> f32
+    where
+        T: OrganismTrait<C>,
+    {
+        let folds = self.calculate_folds(organism);
+
+        folds.iter().sum::<f32>() / folds.len() as f32
+    }
+
+    fn get_fingerprint(&self) -> Option<u64> {
+        let mut hasher = DefaultHasher::new();
+
+        for (train, validation) in &self.folds {
+            train.get_fingerprint()?.hash(&mut hasher);
+            validation.get_fingerprint()?.hash(&mut hasher);
+        }
+
+        Some(hasher.finish())
+    }
+}
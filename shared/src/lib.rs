@@ -1,7 +1,37 @@
-pub use fitness::FitnessTrait;
+pub use cart_pole::CartPole;
+pub use dataset::Dataset;
+pub use double_pole::DoublePole;
+pub use environment::{Environment, Step};
+pub use episode_runner::EpisodeRunner;
+pub use evaluator::{BatchEvaluatorTrait, EvaluatorTrait, FitnessReport, Stateless};
+pub use fitness::{AsyncFitnessTrait, FitnessTrait};
+pub use fitness_cache::FitnessCache;
+pub use islands::{Islands, Topology};
+pub use k_fold::KFold;
+pub use metric::Metric;
+pub use mini_batch::MiniBatch;
+pub use mountain_car::MountainCar;
 pub use organism::{OrganismTrait, OrganismTraitError};
 pub use population::Population;
+pub use reporter::{CsvReporter, EpochMetrics, JsonlReporter, Reporter};
+pub use sequence::Sequence;
+pub use validation::{Validation, ValidationPoint};
 
+mod cart_pole;
+mod dataset;
+mod double_pole;
+mod environment;
+mod episode_runner;
+mod evaluator;
 mod fitness;
+mod fitness_cache;
+mod islands;
+mod k_fold;
+mod metric;
+mod mini_batch;
+mod mountain_car;
 mod organism;
 mod population;
+mod reporter;
+mod sequence;
+mod validation;
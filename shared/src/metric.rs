@@ -0,0 +1,94 @@
+use ndarray::{ArrayView2, Axis};
+
+use vivalaakam_neuro_utils::{argmax, softmax};
+
+/// Error or score between organism outputs and targets.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Metric {
+    /// Mean squared error.
+    #[default]
+    Mse,
+    /// Mean absolute error.
+    Mae,
+    /// Categorical cross-entropy of the row-wise softmax of the outputs.
+    CrossEntropy,
+    /// Share of rows where the largest output matches the largest target.
+    Accuracy,
+    /// Coefficient of determination.
+    R2,
+}
+
+impl Metric {
+    /// Returns true for metrics where lower values are better.
+    pub fn is_error(&self) -> bool {
+        matches!(self, Metric::Mse | Metric::Mae | Metric::CrossEntropy)
+    }
+
+    /// Returns the raw metric value for outputs and targets of the same shape.
+    pub fn evaluate(&self, outputs: ArrayView2<f32>, targets: ArrayView2<f32>) -> f32 {
+        if targets.is_empty() {
+            return 0.0;
+        }
+
+        match self {
+            Metric::Mse => (&targets - &outputs)
+                .mapv(|e| e.powi(2))
+                .mean()
+                .unwrap_or_default(),
+            Metric::Mae => (&targets - &outputs)
+                .mapv(f32::abs)
+                .mean()
+                .unwrap_or_default(),
+            Metric::CrossEntropy => {
+                let total = outputs
+                    .rows()
+                    .into_iter()
+                    .zip(targets.rows())
+                    .map(|(output, target)| {
+                        softmax(output.to_vec())
+                            .iter()
+                            .zip(target.iter())
+                            .map(|(p, t)| -t * p.max(f32::EPSILON).ln())
+                            .sum::<f32>()
+                    })
+                    .sum::<f32>();
+
+                total / targets.nrows() as f32
+            }
+            Metric::Accuracy => {
+                let correct = outputs
+                    .rows()
+                    .into_iter()
+                    .zip(targets.rows())
+                    .filter(|(output, target)| argmax(output.to_vec()) == argmax(target.to_vec()))
+                    .count();
+
+                correct as f32 / targets.nrows() as f32
+            }
+            Metric::R2 => {
+                let mean = targets.mean_axis(Axis(0)).expect("targets are not empty");
+                let residual = (&targets - &outputs).mapv(|e| e.powi(2)).sum();
+                let total = (&targets - &mean).mapv(|e| e.powi(2)).sum();
+
+                if total == 0.0 {
+                    return if residual == 0.0 { 1.0 } else { 0.0 };
+                }
+
+                1.0 - residual / total
+            }
+        }
+    }
+
+    /// Returns the metric as a fitness, where higher is better.
+    ///
+    /// Errors are mapped to `1 / (1 + error)`, scores are returned as is.
+    pub fn fitness(&self, outputs: ArrayView2<f32>, targets: ArrayView2<f32>) -> f32 {
+        let value = self.evaluate(outputs, targets);
+
+        if self.is_error() {
+            1.0 / (1.0 + value)
+        } else {
+            value
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use crate::dataset::Dataset;
+use crate::fitness::FitnessTrait;
+use crate::organism::OrganismTrait;
+
+/// Stochastic fitness on a random subset of a large dataset.
+///
+/// The current batch is shared by every organism until `next_batch` is called,
+/// so organisms scored in the same epoch are compared on the same rows.
+///
+/// It has no fingerprint, so populations with a fitness cache score it without the
+/// cache: a fitness on one batch is not a fitness on the next one.
+#[derive(Debug, Clone)]
+pub struct MiniBatch {
+    dataset: Dataset,
+    size: usize,
+    batch: Dataset,
+}
+
+impl MiniBatch {
+    /// Creates a mini-batch sampler and draws the first batch.
+    pub fn new(dataset: Dataset, size: usize) -> Self {
+        let batch = dataset.sample(size);
+
+        MiniBatch {
+            dataset,
+            size,
+            batch,
+        }
+    }
+
+    /// Draws a fresh batch of random rows.
+    pub fn next_batch(&mut self) -> &Dataset {
+        self.batch = self.dataset.sample(self.size);
+        &self.batch
+    }
+
+    pub fn get_batch(&self) -> &Dataset {
+        &self.batch
+    }
+
+    /// Returns the full dataset, e.g. for re-evaluating elites.
+    pub fn get_dataset(&self) -> &Dataset {
+        &self.dataset
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.size
+    }
+}
+
+impl FitnessTrait for MiniBatch {
+    fn calculate<T, C>(&self, organism: &T) -> f32
+    where
+        T: OrganismTrait<C>,
+    {
+        self.batch.calculate(organism)
+    }
+}
@@ -0,0 +1,73 @@
+use vivalaakam_neuro_utils::argmax;
+use vivalaakam_neuro_utils::random::get_random_range;
+
+use crate::environment::{Environment, Step};
+
+const MIN_POSITION: f32 = -1.2;
+const MAX_POSITION: f32 = 0.6;
+const MAX_SPEED: f32 = 0.07;
+const GOAL_POSITION: f32 = 0.5;
+const FORCE: f32 = 0.001;
+const GRAVITY: f32 = 0.0025;
+
+/// Mountain car (Moore): an underpowered car has to swing up to the right hill.
+///
+/// Observation: `[position, velocity]`. Action: three values, the largest one
+/// selects push left, no push or push right. Reward is -1 per step; the episode
+/// ends when the car reaches position 0.5.
+#[derive(Debug, Clone, Default)]
+pub struct MountainCar {
+    state: [f32; 2],
+}
+
+impl MountainCar {
+    pub fn new() -> Self {
+        MountainCar::default()
+    }
+
+    pub fn get_state(&self) -> [f32; 2] {
+        self.state
+    }
+}
+
+impl Environment for MountainCar {
+    fn reset(&mut self) -> Vec<f32> {
+        self.state = [get_random_range(-0.6, -0.4), 0.0];
+        self.state.to_vec()
+    }
+
+    fn step(&mut self, action: &[f32]) -> Step {
+        let push = if action.is_empty() {
+            0.0
+        } else {
+            argmax(action.to_vec()) as f32 - 1.0
+        };
+
+        let [position, velocity] = self.state;
+
+        let velocity = (velocity + push * FORCE - (3.0 * position).cos() * GRAVITY)
+            .clamp(-MAX_SPEED, MAX_SPEED);
+        let position = (position + velocity).clamp(MIN_POSITION, MAX_POSITION);
+        let velocity = if position == MIN_POSITION && velocity < 0.0 {
+            0.0
+        } else {
+            velocity
+        };
+
+        self.state = [position, velocity];
+
+        Step {
+            observation: self.state.to_vec(),
+            reward: -1.0,
+            done: position >= GOAL_POSITION,
+        }
+    }
+
+    fn get_observation_size(&self) -> usize {
+        2
+    }
+
+    fn get_action_size(&self) -> usize {
+        3
+    }
+}
@@ -2,6 +2,8 @@ use std::error::Error;
 
 use ndarray::Array2;
 
+use crate::evaluator::FitnessReport;
+
 pub struct OrganismTraitError {
     pub cause: Box<dyn Error>,
 }
@@ -15,11 +17,58 @@ impl OrganismTraitError {
 pub trait OrganismTrait<C> {
     fn activate(&self, inputs: Vec<f32>) -> Vec<f32>;
     fn activate_matrix(&self, matrix: &Array2<f32>) -> Array2<f32>;
+    /// Activates the organism row by row, in order, carrying any internal state across rows.
+    fn activate_sequence(&self, sequence: &Array2<f32>) -> Array2<f32> {
+        let outputs = sequence
+            .rows()
+            .into_iter()
+            .map(|row| self.activate(row.to_vec()))
+            .collect::<Vec<_>>();
+
+        let columns = outputs.first().map(|row| row.len()).unwrap_or_default();
+
+        Array2::from_shape_vec((outputs.len(), columns), outputs.concat())
+            .expect("sequence outputs have different lengths")
+    }
+    /// Returns the structural identity of the organism, used to count species.
+    fn get_genotype(&self) -> Vec<u32> {
+        vec![]
+    }
+    /// Returns a content hash of the organism, equal for organisms computing the same function.
+    fn get_hash(&self) -> Option<String> {
+        None
+    }
+    /// Clears internal state (plastic weights, recurrent activations) before a new sequence.
+    fn reset(&self) {}
     fn set_fitness(&self, fitness: f32);
     fn get_fitness(&self) -> f32;
+    /// Stores the result of an `EvaluatorTrait`; the default keeps only the score.
+    fn set_report(&self, report: FitnessReport) {
+        self.set_fitness(report.score)
+    }
+    /// Returns the last stored report with its objectives, behavior and diagnostics.
+    fn get_report(&self) -> Option<FitnessReport> {
+        None
+    }
+    /// Adds a noisy fitness sample (e.g. from a mini-batch) to an exponential moving
+    /// average of the fitness; `smoothing` in `(0, 1]` is the weight of the new sample.
+    fn add_evaluation(&self, fitness: f32, _smoothing: f32) {
+        self.set_fitness(fitness)
+    }
+    /// Returns the number of samples smoothed into the fitness.
+    fn get_evaluations(&self) -> usize {
+        0
+    }
     fn inc_stagnation(&self);
     fn get_stagnation(&self) -> usize;
     fn mutate(&self, other: Option<&Self>, config: &C) -> Result<Self, OrganismTraitError>
     where
         Self: Sized;
+    /// Called by `Population` after every epoch with the surviving organisms, best first,
+    /// to adapt the config, e.g. to switch search phases.
+    fn adapt_config(_config: &mut C, _organisms: &[Self])
+    where
+        Self: Sized,
+    {
+    }
 }
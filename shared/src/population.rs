@@ -1,12 +1,26 @@
+use std::collections::HashSet;
+use std::convert::Infallible;
+
+use futures::stream::{FuturesUnordered, StreamExt};
+
 use vivalaakam_neuro_utils::random::get_random_range;
 
-use crate::fitness::FitnessTrait;
+use crate::evaluator::{BatchEvaluatorTrait, EvaluatorTrait};
+use crate::fitness::{AsyncFitnessTrait, FitnessTrait};
+use crate::fitness_cache::FitnessCache;
 use crate::organism::OrganismTrait;
+use crate::reporter::{Reporter, Reporters};
+use crate::validation::Validation;
 
 pub struct Population<C, T> {
     size: usize,
     organisms: Vec<T>,
     config: C,
+    epoch: usize,
+    dedup: bool,
+    smoothing: f32,
+    cache: Option<FitnessCache>,
+    reporters: Reporters<T>,
 }
 
 impl<C, T> Population<C, T>
@@ -19,9 +33,57 @@ where
             size,
             config,
             organisms: vec![],
+            epoch: 0,
+            dedup: false,
+            smoothing: 0.3,
+            cache: None,
+            reporters: Reporters::new(),
         }
     }
 
+    /// Adds a reporter called on every epoch of the run.
+    pub fn add_reporter(&mut self, reporter: Box<dyn Reporter<T>>) {
+        self.reporters.add(reporter);
+    }
+
+    /// Drops offspring whose `get_hash` matches an organism already in the population,
+    /// so duplicates are neither evaluated nor kept.
+    pub fn set_dedup(&mut self, dedup: bool) {
+        self.dedup = dedup;
+    }
+
+    /// Sets the weight of a new sample in the moving average of `epoch_stochastic`,
+    /// clamped to `(0, 1]`; defaults to `0.3`.
+    pub fn set_fitness_smoothing(&mut self, smoothing: f32) {
+        self.smoothing = smoothing.clamp(f32::EPSILON, 1.0);
+    }
+
+    /// Caches up to `capacity` fitness values by `get_hash`, so identical organisms are
+    /// evaluated once; `0` disables the cache. Only for deterministic fitness with a
+    /// `get_fingerprint`: every epoch but `epoch_stochastic` uses it and skips it when
+    /// the fitness or evaluator has no fingerprint.
+    pub fn set_fitness_cache(&mut self, capacity: usize) {
+        self.cache = (capacity > 0).then(|| FitnessCache::new(capacity));
+    }
+
+    pub fn get_fitness_cache(&self) -> Option<&FitnessCache> {
+        self.cache.as_ref()
+    }
+
+    /// Drops cached fitness values, e.g. after changing an evaluator.
+    ///
+    /// Fitness functions with a `get_fingerprint` invalidate the cache on their own.
+    pub fn invalidate_fitness_cache(&mut self) {
+        if let Some(cache) = self.cache.as_mut() {
+            cache.invalidate();
+        }
+    }
+
+    /// Returns the number of finished epochs.
+    pub fn get_epoch(&self) -> usize {
+        self.epoch
+    }
+
     pub fn len(&self) -> usize {
         self.organisms.len()
     }
@@ -30,6 +92,18 @@ where
         self.organisms.is_empty()
     }
 
+    pub fn get_organisms(&self) -> &[T] {
+        &self.organisms
+    }
+
+    pub fn get_config(&self) -> &C {
+        &self.config
+    }
+
+    pub fn set_config(&mut self, config: C) {
+        self.config = config;
+    }
+
     pub fn add_organism(&mut self, organism: T) {
         self.organisms.push(organism);
     }
@@ -38,42 +112,400 @@ where
     where
         D: FitnessTrait,
     {
+        let mut cache = Self::active_cache(&mut self.cache, dataset.get_fingerprint());
+
         for organism in &self.organisms {
-            organism.set_fitness(dataset.calculate(organism));
+            Self::score_fitness(cache.as_deref_mut(), organism, dataset);
         }
 
         self.cut_costs();
     }
 
+    /// Returns the cache if it can be used for a fitness with this fingerprint.
+    fn active_cache(
+        cache: &mut Option<FitnessCache>,
+        fingerprint: Option<u64>,
+    ) -> Option<&mut FitnessCache> {
+        cache
+            .as_mut()
+            .and_then(|cache| cache.check_fingerprint(fingerprint).then_some(cache))
+    }
+
+    /// Sets the fitness from the cache, or runs `evaluate` and caches the result.
+    ///
+    /// The organism is reset first, so plastic state never carries over between
+    /// evaluations.
+    fn score<E, F>(mut cache: Option<&mut FitnessCache>, organism: &T, evaluate: F) -> Result<(), E>
+    where
+        F: FnOnce(&T) -> Result<(), E>,
+    {
+        if cache
+            .as_deref_mut()
+            .is_some_and(|cache| cache.lookup(organism))
+        {
+            return Ok(());
+        }
+
+        organism.reset();
+        evaluate(organism)?;
+
+        if let Some(cache) = cache {
+            cache.store(organism);
+        }
+
+        Ok(())
+    }
+
+    fn score_fitness<D>(cache: Option<&mut FitnessCache>, organism: &T, dataset: &D)
+    where
+        D: FitnessTrait,
+    {
+        let Ok(()) = Self::score(cache, organism, |organism| {
+            organism.set_fitness(dataset.calculate(organism));
+            Ok::<_, Infallible>(())
+        });
+    }
+
     fn cut_costs(&mut self) {
         self.organisms.sort();
-        self.organisms = self.organisms[0..self.size].to_vec();
+        self.organisms.truncate(self.size);
+    }
+
+    /// Adds organisms migrating from another population: each migrant replaces one of
+    /// the worst residents, whatever its fitness. At most `size` migrants are taken.
+    pub fn immigrate(&mut self, mut organisms: Vec<T>) {
+        organisms.sort();
+        organisms.truncate(self.size);
+
+        self.organisms.sort();
+        self.organisms.truncate(
+            self.organisms
+                .len()
+                .min(self.size)
+                .saturating_sub(organisms.len()),
+        );
+        self.organisms.extend(organisms);
+        self.organisms.sort();
     }
 
     pub fn epoch<D>(&mut self, dataset: &D) -> Option<&T>
     where
         D: FitnessTrait,
     {
-        let size = self.organisms.len();
+        self.start_epoch();
+
+        let offspring = self.offspring();
+        let mut cache = Self::active_cache(&mut self.cache, dataset.get_fingerprint());
 
-        for i in 0..size {
-            let min_j = (size + get_random_range(0, size - 1)) % size;
+        for organism in &offspring {
+            Self::score_fitness(cache.as_deref_mut(), organism, dataset);
+        }
+
+        for organism in offspring {
+            self.add_organism(organism)
+        }
+
+        self.end_epoch()
+    }
+
+    /// Runs an epoch on `dataset`, then scores the best organism on the held-out
+    /// `validation` data and records it in `history`.
+    pub fn epoch_validated<D, V>(
+        &mut self,
+        dataset: &D,
+        validation: &V,
+        history: &mut Validation<T>,
+    ) -> Option<&T>
+    where
+        D: FitnessTrait,
+        V: FitnessTrait,
+    {
+        let epoch = self.epoch;
+        self.epoch(dataset);
+
+        if let Some(best) = self.organisms.first() {
+            history.record(epoch, best, validation);
+        }
+
+        self.organisms.first()
+    }
+
+    /// Runs an epoch on a noisy fitness, e.g. a `MiniBatch`.
+    ///
+    /// Every organism, old and new, is scored on the same current batch and the
+    /// sample is smoothed into its fitness with `add_evaluation`.
+    pub fn epoch_stochastic<D>(&mut self, dataset: &D) -> Option<&T>
+    where
+        D: FitnessTrait,
+    {
+        self.start_epoch();
+
+        for organism in self.offspring() {
+            self.add_organism(organism)
+        }
+
+        for organism in &self.organisms {
+            organism.reset();
+            organism.add_evaluation(dataset.calculate(organism), self.smoothing);
+        }
+
+        self.end_epoch()
+    }
+
+    /// Replaces the smoothed fitness of the `count` best organisms with their fitness
+    /// on the full dataset, correcting for mini-batch noise. Later samples are
+    /// smoothed into the full-dataset fitness.
+    pub fn reevaluate_elites<D>(&mut self, dataset: &D, count: usize)
+    where
+        D: FitnessTrait,
+    {
+        for organism in self.organisms.iter().take(count) {
+            organism.reset();
+            organism.set_fitness(dataset.calculate(organism));
+        }
+
+        self.organisms.sort();
+    }
 
-            if let Ok(organism) = self.organisms[i].mutate(self.organisms.get(min_j), &self.config)
-            {
-                organism.set_fitness(dataset.calculate(&organism));
-                self.add_organism(organism)
+    /// Scores every organism with an evaluator and keeps the best `size` of them.
+    pub fn calculate_fitness_evaluate<E>(&mut self, evaluator: &mut E) -> anyhow::Result<()>
+    where
+        E: EvaluatorTrait<T, C>,
+    {
+        let mut cache = Self::active_cache(&mut self.cache, evaluator.get_fingerprint());
+
+        for organism in &self.organisms {
+            Self::score(cache.as_deref_mut(), organism, |organism| {
+                organism.set_report(evaluator.evaluate(organism)?);
+                anyhow::Ok(())
+            })?;
+        }
+
+        self.cut_costs();
+
+        Ok(())
+    }
+
+    /// Runs an epoch scoring new organisms with an evaluator.
+    ///
+    /// Stops at the first error, before any offspring joins the population, so a failed
+    /// epoch leaves the population and the epoch counter unchanged.
+    pub fn epoch_evaluate<E>(&mut self, evaluator: &mut E) -> anyhow::Result<Option<&T>>
+    where
+        E: EvaluatorTrait<T, C>,
+    {
+        self.start_epoch();
+
+        let offspring = self.offspring();
+        let mut cache = Self::active_cache(&mut self.cache, evaluator.get_fingerprint());
+
+        for organism in &offspring {
+            Self::score(cache.as_deref_mut(), organism, |organism| {
+                organism.set_report(evaluator.evaluate(organism)?);
+                anyhow::Ok(())
+            })?;
+        }
+
+        for organism in offspring {
+            self.add_organism(organism)
+        }
+
+        Ok(self.end_epoch())
+    }
+
+    /// Scores every organism with one batch call and keeps the best `size` of them.
+    pub fn calculate_fitness_batch<E>(&mut self, evaluator: &mut E) -> anyhow::Result<()>
+    where
+        E: BatchEvaluatorTrait<T>,
+    {
+        let cache = Self::active_cache(&mut self.cache, evaluator.get_fingerprint());
+        Self::score_batch(cache, &self.organisms, evaluator)?;
+
+        self.cut_costs();
+
+        Ok(())
+    }
+
+    /// Runs an epoch scoring all new organisms with one batch call.
+    pub fn epoch_batch<E>(&mut self, evaluator: &mut E) -> anyhow::Result<Option<&T>>
+    where
+        E: BatchEvaluatorTrait<T>,
+    {
+        self.start_epoch();
+
+        let offspring = self.offspring();
+        let cache = Self::active_cache(&mut self.cache, evaluator.get_fingerprint());
+        Self::score_batch(cache, &offspring, evaluator)?;
+
+        for organism in offspring {
+            self.add_organism(organism)
+        }
+
+        Ok(self.end_epoch())
+    }
+
+    /// Sets cached fitness values and evaluates the remaining organisms in one batch.
+    fn score_batch<E>(
+        mut cache: Option<&mut FitnessCache>,
+        organisms: &[T],
+        evaluator: &mut E,
+    ) -> anyhow::Result<()>
+    where
+        E: BatchEvaluatorTrait<T>,
+    {
+        let pending = organisms
+            .iter()
+            .filter(|organism| {
+                !cache
+                    .as_deref_mut()
+                    .is_some_and(|cache| cache.lookup(*organism))
+            })
+            .collect::<Vec<_>>();
+
+        let fitness = evaluator.evaluate_batch(&pending)?;
+
+        anyhow::ensure!(
+            fitness.len() == pending.len(),
+            "batch evaluator returned {} values for {} organisms",
+            fitness.len(),
+            pending.len()
+        );
+
+        for (organism, fitness) in pending.into_iter().zip(fitness) {
+            organism.set_fitness(fitness);
+
+            if let Some(cache) = cache.as_deref_mut() {
+                cache.store(organism);
             }
         }
 
+        Ok(())
+    }
+
+    /// Scores every organism asynchronously, at most `concurrency` at a time, and keeps
+    /// the best `size` of them.
+    pub async fn calculate_fitness_async<D>(&mut self, fitness: &D, concurrency: usize)
+    where
+        D: AsyncFitnessTrait,
+        T: Sync,
+    {
+        let cache = Self::active_cache(&mut self.cache, fitness.get_fingerprint());
+        Self::score_async(cache, &self.organisms, fitness, concurrency).await;
+
         self.cut_costs();
+    }
+
+    /// Runs an epoch scoring new organisms asynchronously, at most `concurrency` at a time.
+    pub async fn epoch_async<D>(&mut self, fitness: &D, concurrency: usize) -> Option<&T>
+    where
+        D: AsyncFitnessTrait,
+        T: Sync,
+    {
+        self.start_epoch();
+
+        let offspring = self.offspring();
+        let cache = Self::active_cache(&mut self.cache, fitness.get_fingerprint());
+        Self::score_async(cache, &offspring, fitness, concurrency).await;
+
+        for organism in offspring {
+            self.add_organism(organism)
+        }
+
+        self.end_epoch()
+    }
+
+    async fn score_async<D>(
+        mut cache: Option<&mut FitnessCache>,
+        organisms: &[T],
+        fitness: &D,
+        concurrency: usize,
+    ) where
+        D: AsyncFitnessTrait,
+        T: Sync,
+    {
+        let pending = organisms
+            .iter()
+            .filter(|organism| {
+                !cache
+                    .as_deref_mut()
+                    .is_some_and(|cache| cache.lookup(*organism))
+            })
+            .collect::<Vec<_>>();
+
+        let mut pending = pending.into_iter();
+        let mut running = FuturesUnordered::new();
+
+        loop {
+            while running.len() < concurrency.max(1) {
+                let Some(organism) = pending.next() else {
+                    break;
+                };
+
+                running.push(async move {
+                    organism.reset();
+                    organism.set_fitness(fitness.calculate(organism).await);
+                    organism
+                });
+            }
 
-        match self.organisms.first() {
-            Some(best) => {
-                best.inc_stagnation();
-                Some(best)
+            let Some(organism) = running.next().await else {
+                break;
+            };
+
+            if let Some(cache) = cache.as_deref_mut() {
+                cache.store(organism);
             }
-            _ => None,
         }
     }
+
+    /// Calls `run_end` on every reporter with the current best organism.
+    pub fn finish(&mut self) {
+        self.reporters.run_end(self.epoch, self.organisms.first());
+    }
+
+    /// Mutates every organism with a random partner, dropping duplicates when `dedup` is set.
+    fn offspring(&self) -> Vec<T> {
+        let size = self.organisms.len();
+
+        let mut seen = self
+            .organisms
+            .iter()
+            .filter(|_| self.dedup)
+            .filter_map(|organism| organism.get_hash())
+            .collect::<HashSet<_>>();
+
+        (0..size)
+            .filter_map(|i| {
+                let min_j = (size + get_random_range(0, size - 1)) % size;
+
+                self.organisms[i]
+                    .mutate(self.organisms.get(min_j), &self.config)
+                    .ok()
+            })
+            .filter(|organism| {
+                !self.dedup || organism.get_hash().is_none_or(|hash| seen.insert(hash))
+            })
+            .collect()
+    }
+
+    fn start_epoch(&mut self) {
+        self.reporters.epoch_start(self.epoch);
+    }
+
+    fn end_epoch(&mut self) -> Option<&T> {
+        self.cut_costs();
+        T::adapt_config(&mut self.config, &self.organisms);
+
+        if let Some(best) = self.organisms.first() {
+            best.inc_stagnation();
+        }
+
+        if !self.reporters.is_empty() {
+            self.reporters.epoch_end(self.epoch, &self.organisms);
+        }
+
+        self.epoch += 1;
+
+        self.organisms.first()
+    }
 }
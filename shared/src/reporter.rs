@@ -0,0 +1,217 @@
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use serde::Serialize;
+use tracing::warn;
+
+use crate::organism::OrganismTrait;
+
+/// Hooks called by `Population` during a run.
+///
+/// Every hook has an empty default, so a reporter only implements what it needs.
+/// Errors are logged by the population and do not stop the run.
+pub trait Reporter<T>: Send {
+    fn epoch_start(&mut self, _epoch: usize) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// Called after selection with the epoch metrics and the surviving organisms, best first.
+    fn epoch_end(&mut self, _metrics: &EpochMetrics, _organisms: &[T]) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// Called when the best organism beats the fitness of every previous champion.
+    fn new_champion(&mut self, _epoch: usize, _champion: &T) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// Called when the number of distinct genotypes changes.
+    fn species_change(
+        &mut self,
+        _epoch: usize,
+        _previous: usize,
+        _current: usize,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// Called by `Population::finish` with the best organism.
+    fn run_end(&mut self, _epoch: usize, _champion: Option<&T>) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+/// Per-epoch metrics collected by `Population` and written by `CsvReporter` and `JsonlReporter`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct EpochMetrics {
+    pub epoch: usize,
+    pub size: usize,
+    pub fitness_max: f32,
+    pub fitness_mean: f32,
+    pub fitness_min: f32,
+    pub stagnation: usize,
+    /// Number of distinct genotypes.
+    pub species: usize,
+}
+
+impl EpochMetrics {
+    pub fn new<T, C>(epoch: usize, organisms: &[T], species: usize) -> Self
+    where
+        T: OrganismTrait<C>,
+    {
+        let fitness = organisms
+            .iter()
+            .map(|organism| organism.get_fitness())
+            .collect::<Vec<_>>();
+
+        EpochMetrics {
+            epoch,
+            size: organisms.len(),
+            fitness_max: fitness.iter().copied().fold(f32::MIN, f32::max),
+            fitness_mean: fitness.iter().sum::<f32>() / fitness.len().max(1) as f32,
+            fitness_min: fitness.iter().copied().fold(f32::MAX, f32::min),
+            stagnation: organisms
+                .first()
+                .map(|organism| organism.get_stagnation())
+                .unwrap_or_default(),
+            species,
+        }
+    }
+}
+
+/// Reporters of a run with the champion fitness and species count they were last told about.
+pub(crate) struct Reporters<T> {
+    reporters: Vec<Box<dyn Reporter<T>>>,
+    champion: Option<f32>,
+    species: usize,
+}
+
+impl<T> Reporters<T> {
+    pub fn new() -> Self {
+        Reporters {
+            reporters: vec![],
+            champion: None,
+            species: 0,
+        }
+    }
+
+    pub fn add(&mut self, reporter: Box<dyn Reporter<T>>) {
+        self.reporters.push(reporter);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.reporters.is_empty()
+    }
+
+    pub fn epoch_start(&mut self, epoch: usize) {
+        for reporter in self.reporters.iter_mut() {
+            if let Err(error) = reporter.epoch_start(epoch) {
+                warn!("reporter epoch_start failed: {error}");
+            }
+        }
+    }
+
+    /// Reports the organisms of an epoch, best first.
+    pub fn epoch_end<C>(&mut self, epoch: usize, organisms: &[T])
+    where
+        T: OrganismTrait<C>,
+    {
+        let species = organisms
+            .iter()
+            .map(|organism| organism.get_genotype())
+            .collect::<HashSet<_>>()
+            .len();
+
+        let metrics = EpochMetrics::new(epoch, organisms, species);
+
+        for reporter in self.reporters.iter_mut() {
+            if let Err(error) = reporter.epoch_end(&metrics, organisms) {
+                warn!("reporter epoch_end failed: {error}");
+            }
+        }
+
+        if let Some(best) = organisms.first() {
+            if self
+                .champion
+                .is_none_or(|fitness| best.get_fitness() > fitness)
+            {
+                self.champion = Some(best.get_fitness());
+
+                for reporter in self.reporters.iter_mut() {
+                    if let Err(error) = reporter.new_champion(epoch, best) {
+                        warn!("reporter new_champion failed: {error}");
+                    }
+                }
+            }
+        }
+
+        if species != self.species {
+            for reporter in self.reporters.iter_mut() {
+                if let Err(error) = reporter.species_change(epoch, self.species, species) {
+                    warn!("reporter species_change failed: {error}");
+                }
+            }
+
+            self.species = species;
+        }
+    }
+
+    pub fn run_end(&mut self, epoch: usize, champion: Option<&T>) {
+        for reporter in self.reporters.iter_mut() {
+            if let Err(error) = reporter.run_end(epoch, champion) {
+                warn!("reporter run_end failed: {error}");
+            }
+        }
+    }
+}
+
+/// Writes `EpochMetrics` of every epoch as CSV rows.
+pub struct CsvReporter {
+    writer: csv::Writer<File>,
+}
+
+impl CsvReporter {
+    pub fn new<P>(path: P) -> anyhow::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        Ok(CsvReporter {
+            writer: csv::Writer::from_path(path)?,
+        })
+    }
+}
+
+impl<T> Reporter<T> for CsvReporter {
+    fn epoch_end(&mut self, metrics: &EpochMetrics, _organisms: &[T]) -> anyhow::Result<()> {
+        self.writer.serialize(metrics)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Writes `EpochMetrics` of every epoch as JSON lines.
+pub struct JsonlReporter {
+    writer: BufWriter<File>,
+}
+
+impl JsonlReporter {
+    pub fn new<P>(path: P) -> anyhow::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        Ok(JsonlReporter {
+            writer: BufWriter::new(File::create(path)?),
+        })
+    }
+}
+
+impl<T> Reporter<T> for JsonlReporter {
+    fn epoch_end(&mut self, metrics: &EpochMetrics, _organisms: &[T]) -> anyhow::Result<()> {
+        serde_json::to_writer(&mut self.writer, metrics)?;
+        writeln!(self.writer)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
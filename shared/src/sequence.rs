@@ -0,0 +1,117 @@
+use ndarray::{s, Array2, Axis};
+
+use crate::fitness::FitnessTrait;
+use crate::metric::Metric;
+use crate::organism::OrganismTrait;
+
+/// Sequence fitness: the organism is reset, activated row by row and its outputs
+/// are compared to the targets after `warmup` rows.
+#[derive(Debug, Clone)]
+pub struct Sequence {
+    inputs: Array2<f32>,
+    targets: Array2<f32>,
+    warmup: usize,
+    metric: Metric,
+}
+
+impl Sequence {
+    /// Creates a sequence from inputs and targets with the same number of rows.
+    pub fn new(inputs: Array2<f32>, targets: Array2<f32>) -> Self {
+        assert_eq!(
+            inputs.nrows(),
+            targets.nrows(),
+            "inputs and targets have different lengths"
+        );
+
+        Sequence {
+            inputs,
+            targets,
+            warmup: 0,
+            metric: Metric::default(),
+        }
+    }
+
+    /// One-step-ahead prediction with teacher forcing: row `t` of the series is the
+    /// input and row `t + 1` is the target. Series shorter than two rows give an empty
+    /// sequence.
+    pub fn next_step(series: &Array2<f32>) -> Self {
+        if series.nrows() < 2 {
+            let empty = Array2::zeros((0, series.ncols()));
+            return Sequence::new(empty.clone(), empty);
+        }
+
+        let length = series.nrows() - 1;
+
+        Sequence::new(
+            series.slice(s![..length, ..]).to_owned(),
+            series.slice(s![1.., ..]).to_owned(),
+        )
+    }
+
+    /// Sliding windows over a series: every input row holds `window` consecutive rows
+    /// flattened, the target is the row `horizon` steps after the window.
+    ///
+    /// Panics when `window` is zero.
+    pub fn sliding_window(series: &Array2<f32>, window: usize, horizon: usize) -> Self {
+        assert!(window > 0, "sliding window is empty");
+
+        let columns = series.ncols();
+        let length = (series.nrows() + 1).saturating_sub(window + horizon);
+
+        let inputs = (0..length)
+            .flat_map(|start| series.slice(s![start..start + window, ..]).to_owned())
+            .collect::<Vec<_>>();
+
+        let targets = (0..length)
+            .flat_map(|start| series.row(start + window + horizon - 1).to_vec())
+            .collect::<Vec<_>>();
+
+        Sequence::new(
+            Array2::from_shape_vec((length, window * columns), inputs).expect("window shape"),
+            Array2::from_shape_vec((length, columns), targets).expect("target shape"),
+        )
+    }
+
+    /// Ignores the outputs of the first `warmup` rows.
+    pub fn with_warmup(self, warmup: usize) -> Self {
+        Sequence { warmup, ..self }
+    }
+
+    /// Sets the metric comparing outputs and targets.
+    pub fn with_metric(self, metric: Metric) -> Self {
+        Sequence { metric, ..self }
+    }
+
+    pub fn get_inputs(&self) -> &Array2<f32> {
+        &self.inputs
+    }
+
+    pub fn get_targets(&self) -> &Array2<f32> {
+        &self.targets
+    }
+
+    pub fn len(&self) -> usize {
+        self.inputs.nrows()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inputs.nrows() == 0
+    }
+}
+
+impl FitnessTrait for Sequence {
+    fn calculate<T, C>(&self, organism: &T) -> f32
+    where
+        T: OrganismTrait<C>,
+    {
+        organism.reset();
+
+        let outputs = organism.activate_sequence(&self.inputs);
+        let warmup = self.warmup.min(self.len());
+
+        self.metric.fitness(
+            outputs.slice_axis(Axis(0), (warmup..).into()),
+            self.targets.slice_axis(Axis(0), (warmup..).into()),
+        )
+    }
+}
@@ -0,0 +1,99 @@
+use crate::fitness::FitnessTrait;
+use crate::organism::OrganismTrait;
+
+/// Train and validation fitness of the best organism of an epoch.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ValidationPoint {
+    pub epoch: usize,
+    pub train: f32,
+    pub validation: f32,
+}
+
+/// Overfitting curve of a run and the champion selected by validation fitness.
+///
+/// `Population::epoch_validated` records the best organism of every epoch.
+pub struct Validation<T> {
+    history: Vec<ValidationPoint>,
+    champion: Option<(T, f32)>,
+    best_epoch: usize,
+}
+
+impl<T> Default for Validation<T> {
+    fn default() -> Self {
+        Validation {
+            history: vec![],
+            champion: None,
+            best_epoch: 0,
+        }
+    }
+}
+
+impl<T> Validation<T>
+where
+    T: Clone,
+{
+    pub fn new() -> Self {
+        Validation::default()
+    }
+
+    /// Scores the organism on held-out data and records it for the epoch.
+    ///
+    /// Returns the validation fitness.
+    pub fn record<F, C>(&mut self, epoch: usize, organism: &T, fitness: &F) -> f32
+    where
+        F: FitnessTrait,
+        T: OrganismTrait<C>,
+    {
+        let validation = fitness.calculate(organism);
+
+        self.history.push(ValidationPoint {
+            epoch,
+            train: organism.get_fitness(),
+            validation,
+        });
+
+        if self
+            .champion
+            .as_ref()
+            .is_none_or(|(_, fitness)| validation > *fitness)
+        {
+            self.champion = Some((organism.clone(), validation));
+            self.best_epoch = epoch;
+        }
+
+        validation
+    }
+
+    pub fn get_history(&self) -> &[ValidationPoint] {
+        &self.history
+    }
+
+    /// Returns the organism with the best validation fitness so far.
+    pub fn get_champion(&self) -> Option<&T> {
+        self.champion.as_ref().map(|(organism, _)| organism)
+    }
+
+    pub fn get_champion_fitness(&self) -> Option<f32> {
+        self.champion.as_ref().map(|(_, fitness)| *fitness)
+    }
+
+    /// Returns the epoch the champion was recorded at.
+    pub fn get_best_epoch(&self) -> usize {
+        self.best_epoch
+    }
+
+    /// Returns the number of recorded epochs since the validation fitness last improved.
+    pub fn get_stagnation(&self) -> usize {
+        self.history
+            .last()
+            .map(|point| point.epoch.saturating_sub(self.best_epoch))
+            .unwrap_or_default()
+    }
+
+    /// Returns the difference between train and validation fitness of the last epoch.
+    pub fn get_generalization_gap(&self) -> Option<f32> {
+        self.history
+            .last()
+            .map(|point| point.train - point.validation)
+    }
+}
@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod tests {
+    use ndarray::Array2;
+
+    use vivalaakam_neuro_shared::{Dataset, FitnessTrait, KFold, Metric, MiniBatch};
+
+    #[test]
+    fn from_csv_split() {
+        let dataset = Dataset::from_csv(
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../examples/iris.csv"),
+            &["sepal_length", "sepal_width", "petal_length", "petal_width"],
+            &["variety_sentosa", "variety_versicolor", "variety_virginica"],
+        )
+        .expect("can't load dataset");
+
+        assert_eq!(dataset.len(), 150);
+        assert_eq!(dataset.get_inputs().ncols(), 4);
+        assert_eq!(dataset.get_targets().ncols(), 3);
+
+        let (train, validation, test) = dataset.split(0.2, 0.1);
+        assert_eq!(train.len(), 105);
+        assert_eq!(validation.len(), 30);
+        assert_eq!(test.len(), 15);
+
+        let (train, validation, test) = dataset.split(1.5, 0.5);
+        assert_eq!((train.len(), validation.len(), test.len()), (0, 150, 0));
+
+        assert!(Dataset::from_csv(
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../examples/iris.csv"),
+            &["missing"],
+            &[],
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn k_folds() {
+        let inputs = Array2::from_shape_vec((5, 1), vec![0.0, 1.0, 2.0, 3.0, 4.0]).expect("");
+        let dataset = Dataset::new(inputs.clone(), inputs).expect("");
+
+        let folds = dataset.k_folds(2);
+        assert_eq!(folds.len(), 2);
+        assert_eq!(folds[0].1.get_inputs().column(0).to_vec(), vec![0.0, 1.0]);
+        assert_eq!(
+            folds[0].0.get_inputs().column(0).to_vec(),
+            vec![2.0, 3.0, 4.0]
+        );
+        assert_eq!(folds[1].1.len(), 3);
+
+        let mut k_fold = KFold::new(&dataset, 5);
+        assert_eq!(k_fold.get_folds().len(), 5);
+        assert_eq!(k_fold.get_train().len(), 4);
+        assert_eq!(k_fold.get_validation().get_inputs()[[0, 0]], 0.0);
+
+        k_fold.next_fold();
+        assert_eq!(k_fold.get_fold(), 1);
+        assert_eq!(k_fold.get_validation().get_inputs()[[0, 0]], 1.0);
+    }
+
+    #[test]
+    fn sample() {
+        let inputs = Array2::from_shape_vec((5, 1), vec![0.0, 1.0, 2.0, 3.0, 4.0]).expect("");
+        let dataset = Dataset::new(inputs.clone(), inputs).expect("");
+
+        let mut rows = dataset.sample(3).get_inputs().column(0).to_vec();
+        rows.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        rows.dedup();
+        assert_eq!(rows.len(), 3);
+
+        let mut rows = dataset.sample(10).get_inputs().column(0).to_vec();
+        rows.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(rows, vec![0.0, 1.0, 2.0, 3.0, 4.0]);
+
+        let mut batch = MiniBatch::new(dataset, 2);
+        assert_eq!(batch.next_batch().len(), 2);
+        assert_eq!(batch.get_dataset().len(), 5);
+    }
+
+    #[test]
+    fn new_mismatch() {
+        let inputs = Array2::from_shape_vec((2, 1), vec![0.0, 1.0]).expect("");
+        let targets = Array2::from_shape_vec((1, 1), vec![0.0]).expect("");
+
+        assert!(Dataset::new(inputs, targets).is_err());
+    }
+
+    #[test]
+    fn fingerprint() {
+        let inputs = Array2::from_shape_vec((3, 1), vec![0.0, 1.0, 2.0]).expect("");
+        let dataset = Dataset::new(inputs.clone(), inputs.clone()).expect("");
+
+        assert_eq!(
+            dataset.get_fingerprint(),
+            Dataset::new(inputs.clone(), inputs)
+                .expect("")
+                .get_fingerprint()
+        );
+        assert_ne!(
+            dataset.get_fingerprint(),
+            dataset.select(&[0, 1]).get_fingerprint()
+        );
+        assert_ne!(
+            dataset.get_fingerprint(),
+            dataset.clone().with_metric(Metric::Mae).get_fingerprint()
+        );
+    }
+}
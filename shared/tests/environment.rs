@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use ndarray::Array2;
+
+    use vivalaakam_neuro_shared::{
+        CartPole, DoublePole, EpisodeRunner, FitnessTrait, MountainCar, OrganismTrait,
+        OrganismTraitError,
+    };
+
+    struct Policy {
+        action: fn(Vec<f32>) -> Vec<f32>,
+        fitness: Mutex<f32>,
+    }
+
+    impl Policy {
+        fn new(action: fn(Vec<f32>) -> Vec<f32>) -> Self {
+            Policy {
+                action,
+                fitness: Mutex::new(0.0),
+            }
+        }
+    }
+
+    impl OrganismTrait<()> for Policy {
+        fn activate(&self, inputs: Vec<f32>) -> Vec<f32> {
+            (self.action)(inputs)
+        }
+
+        fn activate_matrix(&self, _matrix: &Array2<f32>) -> Array2<f32> {
+            unimplemented!()
+        }
+
+        fn set_fitness(&self, fitness: f32) {
+            *self.fitness.lock().unwrap() = fitness;
+        }
+
+        fn get_fitness(&self) -> f32 {
+            *self.fitness.lock().unwrap()
+        }
+
+        fn inc_stagnation(&self) {}
+
+        fn get_stagnation(&self) -> usize {
+            0
+        }
+
+        fn mutate(&self, _other: Option<&Self>, _config: &()) -> Result<Self, OrganismTraitError> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn cart_pole() {
+        let runner = EpisodeRunner::new(CartPole::new(), 3, 500);
+
+        let push = Policy::new(|_| vec![1.0]);
+        assert!(runner.calculate(&push) < 100.0);
+
+        let balance = Policy::new(|state| {
+            let control = 0.1 * state[0] + 0.5 * state[1] + 10.0 * state[2] + 2.0 * state[3];
+            vec![if control > 0.0 { 1.0 } else { 0.0 }]
+        });
+        assert_eq!(runner.calculate(&balance), 500.0);
+    }
+
+    #[test]
+    fn double_pole() {
+        let runner = EpisodeRunner::new(DoublePole::new(), 1, 1000);
+
+        let idle = Policy::new(|_| vec![0.5]);
+        assert!(runner.calculate(&idle) < 1000.0);
+    }
+
+    #[test]
+    fn mountain_car() {
+        let runner = EpisodeRunner::new(MountainCar::new(), 3, 200);
+
+        let idle = Policy::new(|_| vec![0.0, 1.0, 0.0]);
+        assert_eq!(runner.calculate(&idle), -200.0);
+
+        let swing = Policy::new(|state| {
+            if state[1] >= 0.0 {
+                vec![0.0, 0.0, 1.0]
+            } else {
+                vec![1.0, 0.0, 0.0]
+            }
+        });
+        assert!(runner.calculate(&swing) > -200.0);
+    }
+}
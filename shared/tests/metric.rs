@@ -0,0 +1,25 @@
+#[cfg(test)]
+mod tests {
+    use ndarray::Array2;
+
+    use vivalaakam_neuro_shared::Metric;
+
+    #[test]
+    fn evaluate() {
+        let targets = Array2::from_shape_vec((2, 2), vec![1.0, 0.0, 0.0, 1.0]).expect("");
+        let outputs = Array2::from_shape_vec((2, 2), vec![0.5, 0.0, 1.0, 0.5]).expect("");
+
+        assert_eq!(Metric::Mse.evaluate(outputs.view(), targets.view()), 0.375);
+        assert_eq!(Metric::Mae.evaluate(outputs.view(), targets.view()), 0.5);
+        assert_eq!(
+            Metric::Accuracy.evaluate(outputs.view(), targets.view()),
+            0.5
+        );
+        assert_eq!(Metric::R2.evaluate(targets.view(), targets.view()), 1.0);
+        assert_eq!(
+            Metric::Mse.fitness(outputs.view(), targets.view()),
+            1.0 / 1.375
+        );
+        assert!(Metric::CrossEntropy.evaluate(targets.view(), targets.view()) > 0.0);
+    }
+}
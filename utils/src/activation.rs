@@ -31,7 +31,7 @@ impl Activation {
             Activation::Relu => x.max(0.0),
             Activation::SoftSign => x / (1.0 + x.abs()),
             Activation::Sinusoid => x.sin(),
-            Activation::Gaussian => (-1.0 * x.powi(2)).exp(),
+            Activation::Gaussian => (-x.powi(2)).exp(),
             Activation::Selu => {
                 let alpha = 1.673_263_2;
                 (if x > 0.0 { x } else { alpha * x.exp() - alpha }) * 1.050_700_9
@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+use strum::{EnumIter, IntoEnumIterator};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, EnumIter, Default)]
+pub enum Aggregation {
+    #[default]
+    Sum,
+    Product,
+    Max,
+    Min,
+    Mean,
+    Median,
+    MaxAbs,
+}
+
+impl Aggregation {
+    /// Aggregates weighted inputs of a neuron; returns `0.0` for no inputs.
+    pub fn aggregate(&self, values: &[f32]) -> f32 {
+        if values.is_empty() {
+            return 0.0;
+        }
+
+        match self {
+            Aggregation::Sum => values.iter().sum(),
+            Aggregation::Product => values.iter().product(),
+            Aggregation::Max => values.iter().fold(f32::MIN, |a, b| a.max(*b)),
+            Aggregation::Min => values.iter().fold(f32::MAX, |a, b| a.min(*b)),
+            Aggregation::Mean => values.iter().sum::<f32>() / values.len() as f32,
+            Aggregation::Median => {
+                let mut sorted = values.to_vec();
+                sorted.sort_by(|a, b| a.total_cmp(b));
+
+                let middle = sorted.len() / 2;
+                if sorted.len().is_multiple_of(2) {
+                    (sorted[middle - 1] + sorted[middle]) / 2.0
+                } else {
+                    sorted[middle]
+                }
+            }
+            Aggregation::MaxAbs => values
+                .iter()
+                .fold(0f32, |a, b| if b.abs() > a.abs() { *b } else { a }),
+        }
+    }
+
+    /// Whether this is `Sum`, the aggregation of nodes written before aggregations existed.
+    pub fn is_sum(&self) -> bool {
+        matches!(self, Aggregation::Sum)
+    }
+
+    pub fn to_vec() -> Vec<Aggregation> {
+        Aggregation::iter().collect::<Vec<_>>()
+    }
+
+    pub fn to_bytes(&self) -> u8 {
+        match self {
+            Aggregation::Sum => 1,
+            Aggregation::Product => 2,
+            Aggregation::Max => 3,
+            Aggregation::Min => 4,
+            Aggregation::Mean => 5,
+            Aggregation::Median => 6,
+            Aggregation::MaxAbs => 7,
+        }
+    }
+
+    pub fn from_bytes(byte: u8) -> Self {
+        match byte {
+            1 => Aggregation::Sum,
+            2 => Aggregation::Product,
+            3 => Aggregation::Max,
+            4 => Aggregation::Min,
+            5 => Aggregation::Mean,
+            6 => Aggregation::Median,
+            7 => Aggregation::MaxAbs,
+            _ => Aggregation::Sum,
+        }
+    }
+}
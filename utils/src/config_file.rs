@@ -0,0 +1,101 @@
+use std::fs;
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Toml(#[from] toml::de::Error),
+    #[error("Unsupported config file extension: {0}")]
+    Format(String),
+    #[error("Invalid value {value:?} in environment variable {key}")]
+    Env { key: String, value: String },
+    #[error("{field} must be in [0, 1], got {value}")]
+    Probability { field: String, value: f32 },
+    #[error("{0}")]
+    Invalid(String),
+}
+
+/// Parses a TOML document; missing fields take the defaults of `T`.
+pub fn from_toml<T>(data: &str) -> Result<T, ConfigError>
+where
+    T: DeserializeOwned,
+{
+    Ok(toml::from_str(data)?)
+}
+
+/// Parses a JSON document; missing fields take the defaults of `T`.
+pub fn from_json<T>(data: &str) -> Result<T, ConfigError>
+where
+    T: DeserializeOwned,
+{
+    Ok(serde_json::from_str(data)?)
+}
+
+/// Loads a `.toml` or `.json` file.
+pub fn from_file<T>(path: &Path) -> Result<T, ConfigError>
+where
+    T: DeserializeOwned,
+{
+    let data = fs::read_to_string(path)?;
+
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("toml") => from_toml(&data),
+        Some("json") => from_json(&data),
+        extension => Err(ConfigError::Format(
+            extension.unwrap_or_default().to_string(),
+        )),
+    }
+}
+
+/// Overrides fields from environment variables named `<prefix><FIELD>`,
+/// e.g. `NEAT_ADD_NODE=0.2` or `NEAT_PHASE=Simplifying` for the prefix `NEAT_`.
+pub fn with_env<T>(config: T, prefix: &str) -> Result<T, ConfigError>
+where
+    T: Serialize + DeserializeOwned,
+{
+    let Value::Object(fields) = serde_json::to_value(&config)? else {
+        return Ok(config);
+    };
+
+    let mut result = Map::new();
+
+    for (field, current) in fields {
+        let key = format!("{prefix}{}", field.to_uppercase());
+
+        let value = match std::env::var(&key) {
+            Ok(raw) => {
+                let value = serde_json::from_str::<Value>(&raw)
+                    .unwrap_or_else(|_| Value::String(raw.clone()));
+
+                if !same_type(&value, &current) {
+                    return Err(ConfigError::Env { key, value: raw });
+                }
+
+                value
+            }
+            Err(_) => current,
+        };
+
+        result.insert(field, value);
+    }
+
+    Ok(serde_json::from_value(Value::Object(result))?)
+}
+
+fn same_type(a: &Value, b: &Value) -> bool {
+    matches!(
+        (a, b),
+        (Value::Number(_), Value::Number(_))
+            | (Value::Bool(_), Value::Bool(_))
+            | (Value::String(_), Value::String(_))
+    )
+}
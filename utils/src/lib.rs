@@ -1,11 +1,15 @@
 pub use activation::Activation;
+pub use aggregation::Aggregation;
 pub use argmax::argmax;
+pub use config_file::ConfigError;
 pub use levenshtein::levenshtein;
 pub use make_id::{make_id, make_u8_id};
 pub use softmax::softmax;
 
 mod activation;
+mod aggregation;
 mod argmax;
+pub mod config_file;
 mod levenshtein;
 mod make_id;
 pub mod random;
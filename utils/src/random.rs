@@ -6,13 +6,21 @@ pub fn get_random_position(len: usize) -> usize {
 }
 
 pub fn get_random_weight(weight: f32) -> f32 {
-    get_random_range(-1f32 * weight, weight)
+    get_random_range(-weight, weight)
 }
 
 pub fn get_random() -> f32 {
     random()
 }
 
+/// Samples a normally distributed value with zero mean and the given standard deviation.
+pub fn get_random_gaussian(sigma: f32) -> f32 {
+    let u1 = 1f32 - random::<f32>();
+    let u2 = random::<f32>();
+
+    sigma * (-2f32 * u1.ln()).sqrt() * (2f32 * std::f32::consts::PI * u2).cos()
+}
+
 pub fn get_random_range<T>(from: T, to: T) -> T
 where
     T: SampleUniform + PartialOrd,